@@ -0,0 +1,314 @@
+use events::{Event, EventType, State};
+use keys::Key;
+use repeat::RepeatEvent;
+use state::InputState;
+
+/// Modifiers held on the seat. Left and right keys are not distinguished.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+impl Modifiers {
+    pub fn from_seat(state: &InputState, seat: &str) -> Modifiers {
+        let down = |left, right| state.is_seat_key_down(seat, left) || state.is_seat_key_down(seat, right);
+
+        Modifiers {
+            ctrl: down(Key::LeftCtrl, Key::RightCtrl),
+            shift: down(Key::LeftShift, Key::RightShift),
+            alt: down(Key::LeftAlt, Key::RightAlt),
+            logo: down(Key::LeftMeta, Key::RightMeta),
+        }
+    }
+}
+
+/// A key pressed while exactly the given modifiers are held.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct Chord {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl Chord {
+    /// Parses e.g. "Ctrl+Alt+F2". Modifier names are case-insensitive:
+    /// Ctrl/Control, Shift, Alt, Super/Logo/Meta/Mod4. The key is parsed with
+    /// Key::from_str.
+    pub fn parse(spec: &str) -> Result<Chord, &'static str> {
+        let mut parts: Vec<&str> = spec.split('+').map(|part| part.trim()).collect();
+        let key = match parts.pop() {
+            Some(key) if !key.is_empty() => key,
+            _ => return Err("Empty key binding"),
+        };
+
+        let mut modifiers = Modifiers::default();
+        for part in parts {
+            let modifier = match part.to_lowercase().as_str() {
+                "ctrl" | "control" => &mut modifiers.ctrl,
+                "shift" => &mut modifiers.shift,
+                "alt" => &mut modifiers.alt,
+                "super" | "logo" | "meta" | "mod4" => &mut modifiers.logo,
+                _ => return Err("Unknown modifier in key binding"),
+            };
+            *modifier = true;
+        }
+
+        let key: Key = key.parse()?;
+        if key.is_modifier() {
+            return Err("Key binding must end in a non-modifier key");
+        }
+
+        Ok(Chord { modifiers: modifiers, key: key })
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Trigger {
+    /// Fires when the last chord is pressed.
+    Press,
+    /// Fires when the key of the last chord is released.
+    Release,
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub sequence: Vec<Chord>,
+    pub action: u32,
+    pub trigger: Trigger,
+    /// Whether matching key events should be withheld from other consumers.
+    pub consume: bool,
+    /// Whether key repeats of the last chord fire the action again.
+    pub repeat: bool,
+}
+
+impl Binding {
+    /// Parses a space separated chord sequence, e.g. "Super+Shift+Enter" or
+    /// "Ctrl+K Ctrl+C". Defaults to a consuming press binding without repeat.
+    pub fn new(spec: &str, action: u32) -> Result<Binding, &'static str> {
+        let sequence = spec.split_whitespace().map(Chord::parse).collect::<Result<Vec<Chord>, _>>()?;
+        if sequence.is_empty() {
+            return Err("Empty key binding");
+        }
+
+        Ok(Binding {
+            sequence: sequence,
+            action: action,
+            trigger: Trigger::Press,
+            consume: true,
+            repeat: false,
+        })
+    }
+}
+
+/// Result of feeding an event to Bindings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Match {
+    /// Actions of the bindings that fired.
+    pub actions: Vec<u32>,
+    /// Whether the event belongs to a consuming binding, including a
+    /// sequence that is still in progress.
+    pub consumed: bool,
+}
+
+/// Matches key events against a set of bindings.
+///
+/// Keeps its own InputState, so feed it every event. Presses of a key that
+/// another keyboard on the seat already holds are ignored.
+#[derive(Default)]
+pub struct Bindings {
+    bindings: Vec<Binding>,
+    state: InputState,
+    // Chords of the sequence typed so far.
+    typed: Vec<Chord>,
+    // Release bindings waiting for their key to come up.
+    armed: Vec<(Key, u32)>,
+    // Keys whose release should be consumed as well.
+    consumed: Vec<Key>,
+}
+
+impl Bindings {
+    pub fn new() -> Bindings {
+        Bindings::default()
+    }
+
+    pub fn add(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+
+    /// Removes all bindings for the action.
+    pub fn remove(&mut self, action: u32) {
+        self.bindings.retain(|binding| binding.action != action);
+    }
+
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    pub fn state(&self) -> &InputState {
+        &self.state
+    }
+
+    pub fn feed(&mut self, event: &Event) -> Match {
+        self.state.feed(event);
+
+        let key_event = match event.event_type() {
+            EventType::Keyboard(key_event) => key_event,
+            _ => return Match::default(),
+        };
+
+        match key_event.state {
+            State::Pressed if key_event.seat_key_count == 1 && !key_event.key.is_modifier() => {
+                let modifiers = Modifiers::from_seat(&self.state, event.device().logical_seat());
+                self.press(Chord { modifiers: modifiers, key: key_event.key })
+            },
+            State::Released if key_event.seat_key_count == 0 => self.release(key_event.key),
+            _ => Match::default(),
+        }
+    }
+
+    /// Fires repeating bindings for a key repeat from KeyRepeat.
+    /// Only single chord press bindings repeat.
+    pub fn feed_repeat(&mut self, repeat: &RepeatEvent) -> Match {
+        let modifiers = Modifiers::from_seat(&self.state, repeat.device.logical_seat());
+        let chord = Chord { modifiers: modifiers, key: repeat.key };
+
+        let mut result = Match::default();
+        for binding in &self.bindings {
+            if binding.repeat && binding.trigger == Trigger::Press && binding.sequence == [chord] {
+                result.actions.push(binding.action);
+                result.consumed |= binding.consume;
+            }
+        }
+
+        result
+    }
+
+    fn press(&mut self, chord: Chord) -> Match {
+        let mut typed = self.typed.clone();
+        typed.push(chord);
+
+        let mut result = self.match_sequence(&typed);
+        if result.is_none() && !self.typed.is_empty() {
+            // The sequence broke off, the chord may start a new one.
+            typed = vec![chord];
+            result = self.match_sequence(&typed);
+        }
+
+        match result {
+            Some((result, complete)) => {
+                self.typed = if complete { Vec::new() } else { typed };
+                if result.consumed {
+                    self.consumed.push(chord.key);
+                }
+                result
+            },
+            None => {
+                self.typed.clear();
+                Match::default()
+            },
+        }
+    }
+
+    // Returns None if no binding starts with the sequence, otherwise the
+    // fired actions and whether a binding completed.
+    fn match_sequence(&mut self, typed: &[Chord]) -> Option<(Match, bool)> {
+        let mut result = Match::default();
+        let mut complete = false;
+        let mut prefix = false;
+
+        for binding in &self.bindings {
+            if binding.sequence[..] == *typed {
+                complete = true;
+                result.consumed |= binding.consume;
+                match binding.trigger {
+                    Trigger::Press => result.actions.push(binding.action),
+                    Trigger::Release => self.armed.push((typed[typed.len() - 1].key, binding.action)),
+                }
+            }
+            else if binding.sequence.starts_with(typed) {
+                prefix = true;
+                result.consumed |= binding.consume;
+            }
+        }
+
+        if complete || prefix {
+            // A completed binding wins over longer sequences sharing its prefix.
+            Some((result, complete))
+        }
+        else {
+            None
+        }
+    }
+
+    fn release(&mut self, key: Key) -> Match {
+        let mut result = Match::default();
+
+        let armed: Vec<(Key, u32)> = self.armed.drain(..).collect();
+        for (armed_key, action) in armed {
+            if armed_key == key {
+                result.actions.push(action);
+            }
+            else {
+                self.armed.push((armed_key, action));
+            }
+        }
+
+        if let Some(index) = self.consumed.iter().position(|&consumed| consumed == key) {
+            self.consumed.remove(index);
+            result.consumed = true;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Binding, Chord, Modifiers};
+    use keys::Key;
+
+    #[test]
+    fn parse_chord() {
+        let chord = Chord::parse("Ctrl+Alt+F2").unwrap();
+
+        assert_eq!(chord.key, Key::F2);
+        assert_eq!(chord.modifiers, Modifiers { ctrl: true, alt: true, ..Modifiers::default() });
+    }
+
+    #[test]
+    fn parse_chord_ignores_case_and_spaces() {
+        let chord = Chord::parse(" super + SHIFT + enter ").unwrap();
+
+        assert_eq!(chord.key, Key::Enter);
+        assert_eq!(chord.modifiers, Modifiers { logo: true, shift: true, ..Modifiers::default() });
+        assert_eq!(Chord::parse("Control+Mod4+a").unwrap().modifiers,
+                   Chord::parse("ctrl+logo+A").unwrap().modifiers);
+    }
+
+    #[test]
+    fn parse_chord_without_modifiers() {
+        let chord = Chord::parse("Esc").unwrap();
+
+        assert_eq!(chord.key, Key::Esc);
+        assert_eq!(chord.modifiers, Modifiers::default());
+    }
+
+    #[test]
+    fn parse_invalid_chords() {
+        assert!(Chord::parse("").is_err());
+        assert!(Chord::parse("Ctrl+").is_err());
+        assert!(Chord::parse("Hyper+A").is_err());
+        assert!(Chord::parse("Ctrl+NoSuchKey").is_err());
+        assert!(Chord::parse("Ctrl+LeftShift").is_err());
+    }
+
+    #[test]
+    fn parse_sequence() {
+        let binding = Binding::new("Ctrl+K Ctrl+C", 1).unwrap();
+
+        assert_eq!(binding.sequence, vec![Chord::parse("Ctrl+K").unwrap(), Chord::parse("Ctrl+C").unwrap()]);
+        assert!(Binding::new("  ", 1).is_err());
+    }
+}
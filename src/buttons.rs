@@ -0,0 +1,136 @@
+event_codes! {
+    /// Pointer, joystick and tablet buttons, the BTN_* range of
+    /// linux/input-event-codes.h.
+    pub enum Button {
+        Num0 = 0x100 => "BTN_0",
+        Num1 = 0x101 => "BTN_1",
+        Num2 = 0x102 => "BTN_2",
+        Num3 = 0x103 => "BTN_3",
+        Num4 = 0x104 => "BTN_4",
+        Num5 = 0x105 => "BTN_5",
+        Num6 = 0x106 => "BTN_6",
+        Num7 = 0x107 => "BTN_7",
+        Num8 = 0x108 => "BTN_8",
+        Num9 = 0x109 => "BTN_9",
+        Left = 0x110 => "BTN_LEFT",
+        Right = 0x111 => "BTN_RIGHT",
+        Middle = 0x112 => "BTN_MIDDLE",
+        Side = 0x113 => "BTN_SIDE",
+        Extra = 0x114 => "BTN_EXTRA",
+        Forward = 0x115 => "BTN_FORWARD",
+        Back = 0x116 => "BTN_BACK",
+        Task = 0x117 => "BTN_TASK",
+        Trigger = 0x120 => "BTN_TRIGGER",
+        Thumb = 0x121 => "BTN_THUMB",
+        Thumb2 = 0x122 => "BTN_THUMB2",
+        Top = 0x123 => "BTN_TOP",
+        Top2 = 0x124 => "BTN_TOP2",
+        Pinkie = 0x125 => "BTN_PINKIE",
+        Base = 0x126 => "BTN_BASE",
+        Base2 = 0x127 => "BTN_BASE2",
+        Base3 = 0x128 => "BTN_BASE3",
+        Base4 = 0x129 => "BTN_BASE4",
+        Base5 = 0x12a => "BTN_BASE5",
+        Base6 = 0x12b => "BTN_BASE6",
+        Dead = 0x12f => "BTN_DEAD",
+        South = 0x130 => "BTN_SOUTH",
+        East = 0x131 => "BTN_EAST",
+        C = 0x132 => "BTN_C",
+        North = 0x133 => "BTN_NORTH",
+        West = 0x134 => "BTN_WEST",
+        Z = 0x135 => "BTN_Z",
+        Tl = 0x136 => "BTN_TL",
+        Tr = 0x137 => "BTN_TR",
+        Tl2 = 0x138 => "BTN_TL2",
+        Tr2 = 0x139 => "BTN_TR2",
+        Select = 0x13a => "BTN_SELECT",
+        Start = 0x13b => "BTN_START",
+        Mode = 0x13c => "BTN_MODE",
+        ThumbL = 0x13d => "BTN_THUMBL",
+        ThumbR = 0x13e => "BTN_THUMBR",
+        ToolPen = 0x140 => "BTN_TOOL_PEN",
+        ToolRubber = 0x141 => "BTN_TOOL_RUBBER",
+        ToolBrush = 0x142 => "BTN_TOOL_BRUSH",
+        ToolPencil = 0x143 => "BTN_TOOL_PENCIL",
+        ToolAirbrush = 0x144 => "BTN_TOOL_AIRBRUSH",
+        ToolFinger = 0x145 => "BTN_TOOL_FINGER",
+        ToolMouse = 0x146 => "BTN_TOOL_MOUSE",
+        ToolLens = 0x147 => "BTN_TOOL_LENS",
+        ToolQuintTap = 0x148 => "BTN_TOOL_QUINTTAP",
+        Stylus3 = 0x149 => "BTN_STYLUS3",
+        Touch = 0x14a => "BTN_TOUCH",
+        Stylus = 0x14b => "BTN_STYLUS",
+        Stylus2 = 0x14c => "BTN_STYLUS2",
+        ToolDoubleTap = 0x14d => "BTN_TOOL_DOUBLETAP",
+        ToolTripleTap = 0x14e => "BTN_TOOL_TRIPLETAP",
+        ToolQuadTap = 0x14f => "BTN_TOOL_QUADTAP",
+        GearDown = 0x150 => "BTN_GEAR_DOWN",
+        GearUp = 0x151 => "BTN_GEAR_UP",
+        DpadUp = 0x220 => "BTN_DPAD_UP",
+        DpadDown = 0x221 => "BTN_DPAD_DOWN",
+        DpadLeft = 0x222 => "BTN_DPAD_LEFT",
+        DpadRight = 0x223 => "BTN_DPAD_RIGHT",
+        TriggerHappy1 = 0x2c0 => "BTN_TRIGGER_HAPPY1",
+        TriggerHappy2 = 0x2c1 => "BTN_TRIGGER_HAPPY2",
+        TriggerHappy3 = 0x2c2 => "BTN_TRIGGER_HAPPY3",
+        TriggerHappy4 = 0x2c3 => "BTN_TRIGGER_HAPPY4",
+        TriggerHappy5 = 0x2c4 => "BTN_TRIGGER_HAPPY5",
+        TriggerHappy6 = 0x2c5 => "BTN_TRIGGER_HAPPY6",
+        TriggerHappy7 = 0x2c6 => "BTN_TRIGGER_HAPPY7",
+        TriggerHappy8 = 0x2c7 => "BTN_TRIGGER_HAPPY8",
+        TriggerHappy9 = 0x2c8 => "BTN_TRIGGER_HAPPY9",
+        TriggerHappy10 = 0x2c9 => "BTN_TRIGGER_HAPPY10",
+        TriggerHappy11 = 0x2ca => "BTN_TRIGGER_HAPPY11",
+        TriggerHappy12 = 0x2cb => "BTN_TRIGGER_HAPPY12",
+        TriggerHappy13 = 0x2cc => "BTN_TRIGGER_HAPPY13",
+        TriggerHappy14 = 0x2cd => "BTN_TRIGGER_HAPPY14",
+        TriggerHappy15 = 0x2ce => "BTN_TRIGGER_HAPPY15",
+        TriggerHappy16 = 0x2cf => "BTN_TRIGGER_HAPPY16",
+        TriggerHappy17 = 0x2d0 => "BTN_TRIGGER_HAPPY17",
+        TriggerHappy18 = 0x2d1 => "BTN_TRIGGER_HAPPY18",
+        TriggerHappy19 = 0x2d2 => "BTN_TRIGGER_HAPPY19",
+        TriggerHappy20 = 0x2d3 => "BTN_TRIGGER_HAPPY20",
+        TriggerHappy21 = 0x2d4 => "BTN_TRIGGER_HAPPY21",
+        TriggerHappy22 = 0x2d5 => "BTN_TRIGGER_HAPPY22",
+        TriggerHappy23 = 0x2d6 => "BTN_TRIGGER_HAPPY23",
+        TriggerHappy24 = 0x2d7 => "BTN_TRIGGER_HAPPY24",
+        TriggerHappy25 = 0x2d8 => "BTN_TRIGGER_HAPPY25",
+        TriggerHappy26 = 0x2d9 => "BTN_TRIGGER_HAPPY26",
+        TriggerHappy27 = 0x2da => "BTN_TRIGGER_HAPPY27",
+        TriggerHappy28 = 0x2db => "BTN_TRIGGER_HAPPY28",
+        TriggerHappy29 = 0x2dc => "BTN_TRIGGER_HAPPY29",
+        TriggerHappy30 = 0x2dd => "BTN_TRIGGER_HAPPY30",
+        TriggerHappy31 = 0x2de => "BTN_TRIGGER_HAPPY31",
+        TriggerHappy32 = 0x2df => "BTN_TRIGGER_HAPPY32",
+        TriggerHappy33 = 0x2e0 => "BTN_TRIGGER_HAPPY33",
+        TriggerHappy34 = 0x2e1 => "BTN_TRIGGER_HAPPY34",
+        TriggerHappy35 = 0x2e2 => "BTN_TRIGGER_HAPPY35",
+        TriggerHappy36 = 0x2e3 => "BTN_TRIGGER_HAPPY36",
+        TriggerHappy37 = 0x2e4 => "BTN_TRIGGER_HAPPY37",
+        TriggerHappy38 = 0x2e5 => "BTN_TRIGGER_HAPPY38",
+        TriggerHappy39 = 0x2e6 => "BTN_TRIGGER_HAPPY39",
+        TriggerHappy40 = 0x2e7 => "BTN_TRIGGER_HAPPY40",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Button;
+
+    #[test]
+    fn display_round_trips() {
+        for code in 0x100..0x300 {
+            let button = Button::from_code(code);
+            assert_eq!(button.to_string().parse(), Ok(button), "{}", button);
+        }
+    }
+
+    #[test]
+    fn parse_names() {
+        for name in &["BTN_LEFT", "btn_left", "left", "Left"] {
+            assert_eq!(name.parse(), Ok(Button::Left), "{}", name);
+        }
+        assert_eq!("0x110".parse(), Ok(Button::Left));
+        assert!("NoSuchButton".parse::<Button>().is_err());
+    }
+}
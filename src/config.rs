@@ -0,0 +1,767 @@
+//! Device configuration, wrapping the libinput_device_config_* calls.
+//!
+//! Setters fail with ConfigError::Unsupported on devices without the option.
+//! Getters of unsupported options return libinput's fallback, usually the
+//! disabled state.
+
+use ::libinput_sys::*;
+use ::std::error::Error;
+use ::std::fmt;
+use ::std::ops::Mul;
+use ::std::os::raw::c_int;
+use buttons::Button;
+use events::Device;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum ConfigError {
+    /// The device doesn't have the option or value.
+    Unsupported,
+    /// The value is out of range.
+    Invalid,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ConfigError::Unsupported => "Unsupported configuration option",
+            ConfigError::Invalid => "Invalid configuration value",
+        })
+    }
+}
+
+impl Error for ConfigError {}
+
+fn status(status: libinput_config_status) -> Result<(), ConfigError> {
+    match status {
+        libinput_config_status::LIBINPUT_CONFIG_STATUS_SUCCESS => Ok(()),
+        libinput_config_status::LIBINPUT_CONFIG_STATUS_UNSUPPORTED => Err(ConfigError::Unsupported),
+        libinput_config_status::LIBINPUT_CONFIG_STATUS_INVALID => Err(ConfigError::Invalid),
+    }
+}
+
+/// Buttons generated by one, two and three finger taps.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum TapButtonMap {
+    /// Left, right, middle.
+    LeftRightMiddle,
+    /// Left, middle, right.
+    LeftMiddleRight,
+}
+
+impl TapButtonMap {
+    fn from_raw(map: libinput_config_tap_button_map) -> TapButtonMap {
+        match map {
+            libinput_config_tap_button_map::LIBINPUT_CONFIG_TAP_MAP_LRM => TapButtonMap::LeftRightMiddle,
+            libinput_config_tap_button_map::LIBINPUT_CONFIG_TAP_MAP_LMR => TapButtonMap::LeftMiddleRight,
+        }
+    }
+
+    fn as_raw(&self) -> libinput_config_tap_button_map {
+        match *self {
+            TapButtonMap::LeftRightMiddle => libinput_config_tap_button_map::LIBINPUT_CONFIG_TAP_MAP_LRM,
+            TapButtonMap::LeftMiddleRight => libinput_config_tap_button_map::LIBINPUT_CONFIG_TAP_MAP_LMR,
+        }
+    }
+}
+
+/// Pointer acceleration profile.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum AccelProfile {
+    /// Constant factor set by the speed.
+    Flat,
+    /// Acceleration depends on how fast the pointer moves.
+    Adaptive,
+}
+
+impl AccelProfile {
+    fn from_raw(profile: libinput_config_accel_profile) -> Option<AccelProfile> {
+        match profile {
+            libinput_config_accel_profile::LIBINPUT_CONFIG_ACCEL_PROFILE_NONE => None,
+            libinput_config_accel_profile::LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => Some(AccelProfile::Flat),
+            libinput_config_accel_profile::LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => Some(AccelProfile::Adaptive),
+        }
+    }
+
+    fn as_raw(&self) -> libinput_config_accel_profile {
+        match *self {
+            AccelProfile::Flat => libinput_config_accel_profile::LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT,
+            AccelProfile::Adaptive => libinput_config_accel_profile::LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE,
+        }
+    }
+}
+
+bitflags! {
+    /// Supported acceleration profiles. The values match libinput_config_accel_profile.
+    pub struct AccelProfiles: u32 {
+        const FLAT = 1 << 0;
+        const ADAPTIVE = 1 << 1;
+    }
+}
+
+/// How a device generates scroll events.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum ScrollMethod {
+    NoScroll,
+    /// Two fingers moving on a touchpad.
+    TwoFinger,
+    /// One finger moving along the edge of a touchpad.
+    Edge,
+    /// Moving the device while the scroll button is held.
+    OnButtonDown,
+}
+
+impl ScrollMethod {
+    fn from_raw(method: libinput_config_scroll_method) -> ScrollMethod {
+        match method {
+            libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_NO_SCROLL => ScrollMethod::NoScroll,
+            libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_2FG => ScrollMethod::TwoFinger,
+            libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_EDGE => ScrollMethod::Edge,
+            libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN => ScrollMethod::OnButtonDown,
+        }
+    }
+
+    fn as_raw(&self) -> libinput_config_scroll_method {
+        match *self {
+            ScrollMethod::NoScroll => libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_NO_SCROLL,
+            ScrollMethod::TwoFinger => libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_2FG,
+            ScrollMethod::Edge => libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_EDGE,
+            ScrollMethod::OnButtonDown => libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN,
+        }
+    }
+}
+
+bitflags! {
+    /// Supported scroll methods. The values match libinput_config_scroll_method.
+    pub struct ScrollMethods: u32 {
+        const TWO_FINGER = 1 << 0;
+        const EDGE = 1 << 1;
+        const ON_BUTTON_DOWN = 1 << 2;
+    }
+}
+
+/// How clicks on a clickpad map to buttons.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum ClickMethod {
+    None,
+    /// The bottom of the pad is split into left, middle and right areas.
+    ButtonAreas,
+    /// The number of fingers on the pad picks the button.
+    Clickfinger,
+}
+
+impl ClickMethod {
+    fn from_raw(method: libinput_config_click_method) -> ClickMethod {
+        match method {
+            libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_NONE => ClickMethod::None,
+            libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS => ClickMethod::ButtonAreas,
+            libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER => ClickMethod::Clickfinger,
+        }
+    }
+
+    fn as_raw(&self) -> libinput_config_click_method {
+        match *self {
+            ClickMethod::None => libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_NONE,
+            ClickMethod::ButtonAreas => libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS,
+            ClickMethod::Clickfinger => libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER,
+        }
+    }
+}
+
+bitflags! {
+    /// Supported click methods. The values match libinput_config_click_method.
+    pub struct ClickMethods: u32 {
+        const BUTTON_AREAS = 1 << 0;
+        const CLICKFINGER = 1 << 1;
+    }
+}
+
+/// Calibration matrix of an absolute device, the top two rows of a 3x3
+/// matrix in row-major order. It maps normalized (0..1) device coordinates:
+/// x' = m[0] * x + m[1] * y + m[2] and y' = m[3] * x + m[4] * y + m[5].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CalibrationMatrix(pub [f32; 6]);
+
+impl CalibrationMatrix {
+    pub fn identity() -> CalibrationMatrix {
+        CalibrationMatrix([1.0, 0.0, 0.0, 0.0, 1.0, 0.0])
+    }
+
+    /// Clockwise rotation by 90 degrees.
+    pub fn rotate_90() -> CalibrationMatrix {
+        CalibrationMatrix([0.0, -1.0, 1.0, 1.0, 0.0, 0.0])
+    }
+
+    pub fn rotate_180() -> CalibrationMatrix {
+        CalibrationMatrix([-1.0, 0.0, 1.0, 0.0, -1.0, 1.0])
+    }
+
+    /// Clockwise rotation by 270 degrees.
+    pub fn rotate_270() -> CalibrationMatrix {
+        CalibrationMatrix([0.0, 1.0, 0.0, -1.0, 0.0, 1.0])
+    }
+
+    /// Mirrors left and right.
+    pub fn flip_horizontal() -> CalibrationMatrix {
+        CalibrationMatrix([-1.0, 0.0, 1.0, 0.0, 1.0, 0.0])
+    }
+
+    /// Mirrors top and bottom.
+    pub fn flip_vertical() -> CalibrationMatrix {
+        CalibrationMatrix([1.0, 0.0, 0.0, 0.0, -1.0, 1.0])
+    }
+
+    /// Maps the whole device onto a rectangle given in normalized coordinates,
+    /// e.g. one screen of a larger layout.
+    pub fn sub_rect(x: f32, y: f32, width: f32, height: f32) -> CalibrationMatrix {
+        CalibrationMatrix([width, 0.0, x, 0.0, height, y])
+    }
+
+    /// This matrix followed by `next`, i.e. `next * self`.
+    pub fn then(&self, next: &CalibrationMatrix) -> CalibrationMatrix {
+        *next * *self
+    }
+
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let m = &self.0;
+        (m[0] * x + m[1] * y + m[2], m[3] * x + m[4] * y + m[5])
+    }
+}
+
+impl Default for CalibrationMatrix {
+    fn default() -> CalibrationMatrix {
+        CalibrationMatrix::identity()
+    }
+}
+
+/// Matrix product: `a * b` applies b first, then a.
+impl Mul for CalibrationMatrix {
+    type Output = CalibrationMatrix;
+
+    fn mul(self, other: CalibrationMatrix) -> CalibrationMatrix {
+        let (a, b) = (&self.0, &other.0);
+        // The implicit third row of both is (0, 0, 1).
+        CalibrationMatrix([
+            a[0] * b[0] + a[1] * b[3],
+            a[0] * b[1] + a[1] * b[4],
+            a[0] * b[2] + a[1] * b[5] + a[2],
+            a[3] * b[0] + a[4] * b[3],
+            a[3] * b[1] + a[4] * b[4],
+            a[3] * b[2] + a[4] * b[5] + a[5],
+        ])
+    }
+}
+
+/// Whether a device sends events.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum SendEventsMode {
+    Enabled,
+    Disabled,
+    /// Disabled while an external pointer device is plugged in, e.g. a
+    /// touchpad while a mouse is connected.
+    DisabledOnExternalMouse,
+}
+
+impl SendEventsMode {
+    fn from_raw(mode: u32) -> SendEventsMode {
+        if mode & libinput_config_send_events_mode::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED as u32 != 0 {
+            SendEventsMode::Disabled
+        }
+        else if mode & libinput_config_send_events_mode::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE as u32 != 0 {
+            SendEventsMode::DisabledOnExternalMouse
+        }
+        else {
+            SendEventsMode::Enabled
+        }
+    }
+
+    fn as_raw(&self) -> libinput_config_send_events_mode {
+        match *self {
+            SendEventsMode::Enabled => libinput_config_send_events_mode::LIBINPUT_CONFIG_SEND_EVENTS_ENABLED,
+            SendEventsMode::Disabled => libinput_config_send_events_mode::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+            SendEventsMode::DisabledOnExternalMouse => {
+                libinput_config_send_events_mode::LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE
+            },
+        }
+    }
+}
+
+bitflags! {
+    /// Supported send-events modes besides Enabled, which every device
+    /// supports. The values match libinput_config_send_events_mode.
+    pub struct SendEventsModes: u32 {
+        const DISABLED = 1 << 0;
+        const DISABLED_ON_EXTERNAL_MOUSE = 1 << 1;
+    }
+}
+
+/// Tap-to-click.
+impl Device {
+    /// Number of fingers that can tap, 0 if tapping isn't supported.
+    pub fn tap_finger_count(&self) -> u32 {
+        unsafe { libinput_device_config_tap_get_finger_count(self.as_raw()) as u32 }
+    }
+
+    pub fn set_tap_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        let state = if enabled {
+            libinput_config_tap_state::LIBINPUT_CONFIG_TAP_ENABLED
+        }
+        else {
+            libinput_config_tap_state::LIBINPUT_CONFIG_TAP_DISABLED
+        };
+        status(unsafe { libinput_device_config_tap_set_enabled(self.as_raw(), state) })
+    }
+
+    pub fn tap_enabled(&self) -> bool {
+        unsafe { libinput_device_config_tap_get_enabled(self.as_raw()) == libinput_config_tap_state::LIBINPUT_CONFIG_TAP_ENABLED }
+    }
+
+    pub fn tap_default_enabled(&self) -> bool {
+        unsafe { libinput_device_config_tap_get_default_enabled(self.as_raw()) == libinput_config_tap_state::LIBINPUT_CONFIG_TAP_ENABLED }
+    }
+
+    pub fn set_tap_button_map(&self, map: TapButtonMap) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_tap_set_button_map(self.as_raw(), map.as_raw()) })
+    }
+
+    pub fn tap_button_map(&self) -> TapButtonMap {
+        TapButtonMap::from_raw(unsafe { libinput_device_config_tap_get_button_map(self.as_raw()) })
+    }
+
+    pub fn tap_default_button_map(&self) -> TapButtonMap {
+        TapButtonMap::from_raw(unsafe { libinput_device_config_tap_get_default_button_map(self.as_raw()) })
+    }
+
+    /// Tap-and-drag: tap, then put the finger down again to drag.
+    pub fn set_tap_drag_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        let state = if enabled {
+            libinput_config_drag_state::LIBINPUT_CONFIG_DRAG_ENABLED
+        }
+        else {
+            libinput_config_drag_state::LIBINPUT_CONFIG_DRAG_DISABLED
+        };
+        status(unsafe { libinput_device_config_tap_set_drag_enabled(self.as_raw(), state) })
+    }
+
+    pub fn tap_drag_enabled(&self) -> bool {
+        unsafe { libinput_device_config_tap_get_drag_enabled(self.as_raw()) == libinput_config_drag_state::LIBINPUT_CONFIG_DRAG_ENABLED }
+    }
+
+    pub fn tap_default_drag_enabled(&self) -> bool {
+        unsafe { libinput_device_config_tap_get_default_drag_enabled(self.as_raw()) == libinput_config_drag_state::LIBINPUT_CONFIG_DRAG_ENABLED }
+    }
+
+    /// Drag lock: a drag continues for a moment after the finger is lifted.
+    pub fn set_tap_drag_lock_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        let state = if enabled {
+            libinput_config_drag_lock_state::LIBINPUT_CONFIG_DRAG_LOCK_ENABLED
+        }
+        else {
+            libinput_config_drag_lock_state::LIBINPUT_CONFIG_DRAG_LOCK_DISABLED
+        };
+        status(unsafe { libinput_device_config_tap_set_drag_lock_enabled(self.as_raw(), state) })
+    }
+
+    pub fn tap_drag_lock_enabled(&self) -> bool {
+        unsafe { libinput_device_config_tap_get_drag_lock_enabled(self.as_raw()) == libinput_config_drag_lock_state::LIBINPUT_CONFIG_DRAG_LOCK_ENABLED }
+    }
+
+    pub fn tap_default_drag_lock_enabled(&self) -> bool {
+        unsafe { libinput_device_config_tap_get_default_drag_lock_enabled(self.as_raw()) == libinput_config_drag_lock_state::LIBINPUT_CONFIG_DRAG_LOCK_ENABLED }
+    }
+}
+
+/// Pointer acceleration.
+impl Device {
+    pub fn accel_is_available(&self) -> bool {
+        unsafe { libinput_device_config_accel_is_available(self.as_raw()) != 0 }
+    }
+
+    /// Speed from -1 (slowest) to 1 (fastest), 0 being the default.
+    pub fn set_accel_speed(&self, speed: f64) -> Result<(), ConfigError> {
+        if !(-1.0..=1.0).contains(&speed) {
+            return Err(ConfigError::Invalid);
+        }
+
+        status(unsafe { libinput_device_config_accel_set_speed(self.as_raw(), speed) })
+    }
+
+    pub fn accel_speed(&self) -> f64 {
+        unsafe { libinput_device_config_accel_get_speed(self.as_raw()) }
+    }
+
+    pub fn default_accel_speed(&self) -> f64 {
+        unsafe { libinput_device_config_accel_get_default_speed(self.as_raw()) }
+    }
+
+    pub fn accel_profiles(&self) -> AccelProfiles {
+        AccelProfiles::from_bits_truncate(unsafe { libinput_device_config_accel_get_profiles(self.as_raw()) })
+    }
+
+    pub fn set_accel_profile(&self, profile: AccelProfile) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_accel_set_profile(self.as_raw(), profile.as_raw()) })
+    }
+
+    /// None if the device has no acceleration profiles.
+    pub fn accel_profile(&self) -> Option<AccelProfile> {
+        AccelProfile::from_raw(unsafe { libinput_device_config_accel_get_profile(self.as_raw()) })
+    }
+
+    pub fn default_accel_profile(&self) -> Option<AccelProfile> {
+        AccelProfile::from_raw(unsafe { libinput_device_config_accel_get_default_profile(self.as_raw()) })
+    }
+}
+
+/// Scrolling.
+impl Device {
+    pub fn has_natural_scroll(&self) -> bool {
+        unsafe { libinput_device_config_scroll_has_natural_scroll(self.as_raw()) != 0 }
+    }
+
+    /// Natural scrolling moves the content with the fingers.
+    pub fn set_natural_scroll_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_scroll_set_natural_scroll_enabled(self.as_raw(), enabled as c_int) })
+    }
+
+    pub fn natural_scroll_enabled(&self) -> bool {
+        unsafe { libinput_device_config_scroll_get_natural_scroll_enabled(self.as_raw()) != 0 }
+    }
+
+    pub fn default_natural_scroll_enabled(&self) -> bool {
+        unsafe { libinput_device_config_scroll_get_default_natural_scroll_enabled(self.as_raw()) != 0 }
+    }
+
+    pub fn scroll_methods(&self) -> ScrollMethods {
+        ScrollMethods::from_bits_truncate(unsafe { libinput_device_config_scroll_get_methods(self.as_raw()) })
+    }
+
+    pub fn set_scroll_method(&self, method: ScrollMethod) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_scroll_set_method(self.as_raw(), method.as_raw()) })
+    }
+
+    pub fn scroll_method(&self) -> ScrollMethod {
+        ScrollMethod::from_raw(unsafe { libinput_device_config_scroll_get_method(self.as_raw()) })
+    }
+
+    pub fn default_scroll_method(&self) -> ScrollMethod {
+        ScrollMethod::from_raw(unsafe { libinput_device_config_scroll_get_default_method(self.as_raw()) })
+    }
+
+    /// Button for ScrollMethod::OnButtonDown. None disables button scrolling.
+    pub fn set_scroll_button(&self, button: Option<Button>) -> Result<(), ConfigError> {
+        let code = button.map_or(0, |button| button.code());
+        status(unsafe { libinput_device_config_scroll_set_button(self.as_raw(), code) })
+    }
+
+    pub fn scroll_button(&self) -> Option<Button> {
+        match unsafe { libinput_device_config_scroll_get_button(self.as_raw()) } {
+            0 => None,
+            code => Some(Button::from_code(code)),
+        }
+    }
+
+    pub fn default_scroll_button(&self) -> Option<Button> {
+        match unsafe { libinput_device_config_scroll_get_default_button(self.as_raw()) } {
+            0 => None,
+            code => Some(Button::from_code(code)),
+        }
+    }
+}
+
+/// Clicking.
+impl Device {
+    pub fn click_methods(&self) -> ClickMethods {
+        ClickMethods::from_bits_truncate(unsafe { libinput_device_config_click_get_methods(self.as_raw()) })
+    }
+
+    pub fn set_click_method(&self, method: ClickMethod) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_click_set_method(self.as_raw(), method.as_raw()) })
+    }
+
+    pub fn click_method(&self) -> ClickMethod {
+        ClickMethod::from_raw(unsafe { libinput_device_config_click_get_method(self.as_raw()) })
+    }
+
+    pub fn default_click_method(&self) -> ClickMethod {
+        ClickMethod::from_raw(unsafe { libinput_device_config_click_get_default_method(self.as_raw()) })
+    }
+
+    pub fn middle_emulation_is_available(&self) -> bool {
+        unsafe { libinput_device_config_middle_emulation_is_available(self.as_raw()) != 0 }
+    }
+
+    /// Middle button emulation: pressing left and right together clicks the
+    /// middle button.
+    pub fn set_middle_emulation_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        let state = if enabled {
+            libinput_config_middle_emulation_state::LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED
+        }
+        else {
+            libinput_config_middle_emulation_state::LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED
+        };
+        status(unsafe { libinput_device_config_middle_emulation_set_enabled(self.as_raw(), state) })
+    }
+
+    pub fn middle_emulation_enabled(&self) -> bool {
+        unsafe {
+            libinput_device_config_middle_emulation_get_enabled(self.as_raw())
+                == libinput_config_middle_emulation_state::LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED
+        }
+    }
+
+    pub fn default_middle_emulation_enabled(&self) -> bool {
+        unsafe {
+            libinput_device_config_middle_emulation_get_default_enabled(self.as_raw())
+                == libinput_config_middle_emulation_state::LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED
+        }
+    }
+}
+
+/// Left-handed mode, rotation and disable-while-typing.
+impl Device {
+    pub fn left_handed_is_available(&self) -> bool {
+        unsafe { libinput_device_config_left_handed_is_available(self.as_raw()) != 0 }
+    }
+
+    /// Left-handed mode swaps the left and right buttons.
+    pub fn set_left_handed(&self, left_handed: bool) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_left_handed_set(self.as_raw(), left_handed as c_int) })
+    }
+
+    pub fn left_handed(&self) -> bool {
+        unsafe { libinput_device_config_left_handed_get(self.as_raw()) != 0 }
+    }
+
+    pub fn default_left_handed(&self) -> bool {
+        unsafe { libinput_device_config_left_handed_get_default(self.as_raw()) != 0 }
+    }
+
+    pub fn rotation_is_available(&self) -> bool {
+        unsafe { libinput_device_config_rotation_is_available(self.as_raw()) != 0 }
+    }
+
+    /// Rotates relative motion clockwise by `degrees`, 0 to 359. Devices may
+    /// only support some angles, e.g. multiples of 90.
+    pub fn set_rotation_angle(&self, degrees: u32) -> Result<(), ConfigError> {
+        if degrees >= 360 {
+            return Err(ConfigError::Invalid);
+        }
+
+        status(unsafe { libinput_device_config_rotation_set_angle(self.as_raw(), degrees) })
+    }
+
+    pub fn rotation_angle(&self) -> u32 {
+        unsafe { libinput_device_config_rotation_get_angle(self.as_raw()) }
+    }
+
+    pub fn default_rotation_angle(&self) -> u32 {
+        unsafe { libinput_device_config_rotation_get_default_angle(self.as_raw()) }
+    }
+
+    pub fn dwt_is_available(&self) -> bool {
+        unsafe { libinput_device_config_dwt_is_available(self.as_raw()) != 0 }
+    }
+
+    /// Disable-while-typing: ignores the touchpad while typing on the keyboard.
+    pub fn set_dwt_enabled(&self, enabled: bool) -> Result<(), ConfigError> {
+        let state = if enabled {
+            libinput_config_dwt_state::LIBINPUT_CONFIG_DWT_ENABLED
+        }
+        else {
+            libinput_config_dwt_state::LIBINPUT_CONFIG_DWT_DISABLED
+        };
+        status(unsafe { libinput_device_config_dwt_set_enabled(self.as_raw(), state) })
+    }
+
+    pub fn dwt_enabled(&self) -> bool {
+        unsafe { libinput_device_config_dwt_get_enabled(self.as_raw()) == libinput_config_dwt_state::LIBINPUT_CONFIG_DWT_ENABLED }
+    }
+
+    pub fn default_dwt_enabled(&self) -> bool {
+        unsafe { libinput_device_config_dwt_get_default_enabled(self.as_raw()) == libinput_config_dwt_state::LIBINPUT_CONFIG_DWT_ENABLED }
+    }
+}
+
+/// Calibration of absolute devices.
+impl Device {
+    pub fn has_calibration_matrix(&self) -> bool {
+        unsafe { libinput_device_config_calibration_has_matrix(self.as_raw()) != 0 }
+    }
+
+    pub fn set_calibration_matrix(&self, matrix: &CalibrationMatrix) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_calibration_set_matrix(self.as_raw(), matrix.0.as_ptr()) })
+    }
+
+    /// The identity for devices without calibration.
+    pub fn calibration_matrix(&self) -> CalibrationMatrix {
+        let mut matrix = CalibrationMatrix::identity();
+        unsafe { libinput_device_config_calibration_get_matrix(self.as_raw(), matrix.0.as_mut_ptr()) };
+        matrix
+    }
+
+    /// The matrix from the LIBINPUT_CALIBRATION_MATRIX udev property, or the
+    /// identity.
+    pub fn default_calibration_matrix(&self) -> CalibrationMatrix {
+        let mut matrix = CalibrationMatrix::identity();
+        unsafe { libinput_device_config_calibration_get_default_matrix(self.as_raw(), matrix.0.as_mut_ptr()) };
+        matrix
+    }
+}
+
+/// Enabling and disabling devices.
+impl Device {
+    pub fn send_events_modes(&self) -> SendEventsModes {
+        SendEventsModes::from_bits_truncate(unsafe { libinput_device_config_send_events_get_modes(self.as_raw()) })
+    }
+
+    /// A disabled device stops sending events, but stays in the context and
+    /// can be enabled again. Held keys and buttons are released first.
+    pub fn set_send_events(&self, mode: SendEventsMode) -> Result<(), ConfigError> {
+        status(unsafe { libinput_device_config_send_events_set_mode(self.as_raw(), mode.as_raw() as u32) })
+    }
+
+    pub fn send_events(&self) -> SendEventsMode {
+        SendEventsMode::from_raw(unsafe { libinput_device_config_send_events_get_mode(self.as_raw()) })
+    }
+
+    pub fn default_send_events(&self) -> SendEventsMode {
+        SendEventsMode::from_raw(unsafe { libinput_device_config_send_events_get_default_mode(self.as_raw()) })
+    }
+}
+
+/// Configuration applied to devices as they are added, see
+/// LibInput::options_mut(). Options left at None keep the device default.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct Options {
+    pub tap: Option<bool>,
+    pub tap_button_map: Option<TapButtonMap>,
+    pub tap_drag: Option<bool>,
+    pub tap_drag_lock: Option<bool>,
+    pub natural_scroll: Option<bool>,
+    pub scroll_method: Option<ScrollMethod>,
+    pub scroll_button: Option<Button>,
+    pub click_method: Option<ClickMethod>,
+    pub middle_emulation: Option<bool>,
+    pub left_handed: Option<bool>,
+    pub dwt: Option<bool>,
+    pub accel_speed: Option<f64>,
+    pub accel_profile: Option<AccelProfile>,
+}
+
+// Applies the options that are set to a newly added device. Devices without
+// an option ignore it.
+pub(crate) fn apply_options(device: &Device, options: &Options) {
+    if let Some(enabled) = options.tap {
+        let _ = device.set_tap_enabled(enabled);
+    }
+    if let Some(map) = options.tap_button_map {
+        let _ = device.set_tap_button_map(map);
+    }
+    if let Some(enabled) = options.tap_drag {
+        let _ = device.set_tap_drag_enabled(enabled);
+    }
+    if let Some(enabled) = options.tap_drag_lock {
+        let _ = device.set_tap_drag_lock_enabled(enabled);
+    }
+    if let Some(enabled) = options.natural_scroll {
+        let _ = device.set_natural_scroll_enabled(enabled);
+    }
+    if let Some(method) = options.scroll_method {
+        let _ = device.set_scroll_method(method);
+    }
+    if let Some(button) = options.scroll_button {
+        let _ = device.set_scroll_button(Some(button));
+    }
+    if let Some(method) = options.click_method {
+        let _ = device.set_click_method(method);
+    }
+    if let Some(enabled) = options.middle_emulation {
+        let _ = device.set_middle_emulation_enabled(enabled);
+    }
+    if let Some(left_handed) = options.left_handed {
+        let _ = device.set_left_handed(left_handed);
+    }
+    if let Some(enabled) = options.dwt {
+        let _ = device.set_dwt_enabled(enabled);
+    }
+    if let Some(speed) = options.accel_speed {
+        let _ = device.set_accel_speed(speed);
+    }
+    if let Some(profile) = options.accel_profile {
+        let _ = device.set_accel_profile(profile);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CalibrationMatrix;
+    use mapping::Transform;
+
+    #[test]
+    fn rotations_compose() {
+        let rotate_90 = CalibrationMatrix::rotate_90();
+
+        assert_eq!(rotate_90.then(&rotate_90), CalibrationMatrix::rotate_180());
+        assert_eq!(rotate_90.then(&CalibrationMatrix::rotate_180()), CalibrationMatrix::rotate_270());
+        assert_eq!(CalibrationMatrix::rotate_270().then(&rotate_90), CalibrationMatrix::identity());
+    }
+
+    #[test]
+    fn rotations_move_corners_clockwise() {
+        assert_eq!(CalibrationMatrix::rotate_90().apply(0.0, 0.0), (1.0, 0.0));
+        assert_eq!(CalibrationMatrix::rotate_180().apply(0.0, 0.0), (1.0, 1.0));
+        assert_eq!(CalibrationMatrix::rotate_270().apply(0.0, 0.0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn flips_compose() {
+        let flip_horizontal = CalibrationMatrix::flip_horizontal();
+        let flip_vertical = CalibrationMatrix::flip_vertical();
+
+        assert_eq!(flip_horizontal.then(&flip_horizontal), CalibrationMatrix::identity());
+        assert_eq!(flip_vertical.then(&flip_vertical), CalibrationMatrix::identity());
+        assert_eq!(flip_horizontal.then(&flip_vertical), CalibrationMatrix::rotate_180());
+        // Rotating and then mirroring swaps the axes.
+        assert_eq!(CalibrationMatrix::rotate_90().then(&flip_horizontal),
+                   CalibrationMatrix([0.0, 1.0, 0.0, 1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn sub_rect_maps_corners() {
+        let right_half = CalibrationMatrix::sub_rect(0.5, 0.0, 0.5, 1.0);
+
+        assert_eq!(right_half.apply(0.0, 0.0), (0.5, 0.0));
+        assert_eq!(right_half.apply(1.0, 1.0), (1.0, 1.0));
+        assert_eq!(CalibrationMatrix::rotate_90().then(&right_half),
+                   CalibrationMatrix([0.0, -0.5, 1.0, 1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn mul_applies_right_operand_first() {
+        let rotate_90 = CalibrationMatrix::rotate_90();
+        let flip_horizontal = CalibrationMatrix::flip_horizontal();
+
+        assert_eq!(flip_horizontal * rotate_90, rotate_90.then(&flip_horizontal));
+        assert_eq!(CalibrationMatrix::identity() * rotate_90, rotate_90);
+        assert_eq!(rotate_90 * CalibrationMatrix::identity(), rotate_90);
+    }
+
+    #[test]
+    fn matrices_match_transforms() {
+        let flip = CalibrationMatrix::flip_horizontal();
+        let transforms = [
+            (Transform::Normal, CalibrationMatrix::identity()),
+            (Transform::Rotate90, CalibrationMatrix::rotate_90()),
+            (Transform::Rotate180, CalibrationMatrix::rotate_180()),
+            (Transform::Rotate270, CalibrationMatrix::rotate_270()),
+            (Transform::Flipped, flip),
+            (Transform::Flipped90, flip.then(&CalibrationMatrix::rotate_90())),
+            (Transform::Flipped180, flip.then(&CalibrationMatrix::rotate_180())),
+            (Transform::Flipped270, flip.then(&CalibrationMatrix::rotate_270())),
+        ];
+
+        for &(transform, matrix) in &transforms {
+            let (x, y) = matrix.apply(0.25, 0.5);
+            assert_eq!(transform.apply(0.25, 0.5), (x as f64, y as f64), "{:?}", transform);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use events::{Event, EventType};
+use mapping::{OutputMap, Transform};
+
+/// Axis aligned rectangle in layout coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect { x: x, y: y, width: width, height: height }
+    }
+
+    /// Whether the point lies inside, the right and bottom edges excluded.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Closest point inside the rectangle. Points past the right or bottom
+    /// edge end up a rounding error short of it, so contains() holds for the
+    /// result of any non-empty rectangle.
+    pub fn clamp(&self, x: f64, y: f64) -> (f64, f64) {
+        let max_x = just_below(self.x + self.width).max(self.x);
+        let max_y = just_below(self.y + self.height).max(self.y);
+
+        (x.max(self.x).min(max_x), y.max(self.y).min(max_y))
+    }
+
+    /// Smallest rectangle containing both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+
+        Rect::new(x, y, right - x, bottom - y)
+    }
+}
+
+/// A named output (screen) placed in the layout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Output {
+    pub name: String,
+    pub rect: Rect,
+    /// Applied to absolute devices mapped to this output.
+    pub transform: Transform,
+}
+
+impl Output {
+    pub fn new(name: &str, rect: Rect) -> Output {
+        Output { name: name.to_owned(), rect: rect, transform: Transform::Normal }
+    }
+}
+
+/// Pointer position on a layout of outputs.
+///
+/// Relative motion is integrated and absolute devices are mapped through the
+/// OutputMap, by default onto the output named by the device or else the
+/// bounding box of the layout. The cursor is always kept on an output: a
+/// position in a gap between outputs snaps to the closest point of the
+/// nearest one.
+pub struct Cursor {
+    x: f64,
+    y: f64,
+    outputs: Vec<Output>,
+    output_map: OutputMap,
+}
+
+impl Cursor {
+    /// Starts in the center of the first output.
+    pub fn new(outputs: Vec<Output>) -> Cursor {
+        let (x, y) = match outputs.first() {
+            Some(output) => (output.rect.x + output.rect.width / 2.0,
+                             output.rect.y + output.rect.height / 2.0),
+            None => (0.0, 0.0),
+        };
+
+        Cursor { x: x, y: y, outputs: outputs, output_map: OutputMap::new() }
+    }
+
+    pub fn position(&self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    pub fn outputs(&self) -> &[Output] {
+        &self.outputs
+    }
+
+    /// Replaces the layout, e.g. after a hotplug, and clamps the cursor to it.
+    pub fn set_outputs(&mut self, outputs: Vec<Output>) {
+        self.outputs = outputs;
+        let (x, y) = (self.x, self.y);
+        self.warp(x, y);
+    }
+
+    pub fn output_map(&self) -> &OutputMap {
+        &self.output_map
+    }
+
+    pub fn set_output_map(&mut self, output_map: OutputMap) {
+        self.output_map = output_map;
+    }
+
+    /// Output under the cursor. None only if the layout is empty.
+    pub fn output(&self) -> Option<&Output> {
+        self.outputs.iter().find(|output| output.rect.contains(self.x, self.y))
+    }
+
+    /// Bounding box of all outputs.
+    pub fn bounds(&self) -> Option<Rect> {
+        bounds(&self.outputs)
+    }
+
+    /// Moves the cursor to the point on the layout closest to (x, y).
+    pub fn warp(&mut self, x: f64, y: f64) {
+        let (x, y) = self.clamp(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Updates the position from pointer and tablet tool events.
+    /// Returns whether the event moved the cursor.
+    pub fn feed(&mut self, event: &Event) -> bool {
+        let (x, y) = match event.event_type() {
+            EventType::MouseMove(motion) => (self.x + motion.dx, self.y + motion.dy),
+            EventType::MouseMoveAbsolute(_)
+            | EventType::TabletAxis
+            | EventType::TabletProximity
+            | EventType::TabletTip => {
+                match self.output_map.map(event, &self.outputs) {
+                    Some(position) => position,
+                    None => return false,
+                }
+            },
+            _ => return false,
+        };
+
+        let old = (self.x, self.y);
+        self.warp(x, y);
+        old != (self.x, self.y)
+    }
+
+    fn clamp(&self, x: f64, y: f64) -> (f64, f64) {
+        if self.outputs.iter().any(|output| output.rect.contains(x, y)) {
+            return (x, y);
+        }
+
+        let distance = |&(cx, cy): &(f64, f64)| (cx - x) * (cx - x) + (cy - y) * (cy - y);
+
+        self.outputs.iter()
+            .map(|output| output.rect.clamp(x, y))
+            .fold(None, |closest: Option<(f64, f64)>, point| match closest {
+                Some(closest) if distance(&closest) <= distance(&point) => Some(closest),
+                _ => Some(point),
+            })
+            .unwrap_or((x, y))
+    }
+}
+
+// A value a rounding error below the edge. The step is at least one unit in
+// the last place, so the result always compares less than the edge.
+fn just_below(edge: f64) -> f64 {
+    edge - edge.abs().max(1.0) * ::std::f64::EPSILON
+}
+
+/// Bounding box of all outputs, None if there are none.
+pub fn bounds(outputs: &[Output]) -> Option<Rect> {
+    let mut outputs = outputs.iter();
+    let first = match outputs.next() {
+        Some(output) => output.rect,
+        None => return None,
+    };
+
+    Some(outputs.fold(first, |bounds, output| bounds.union(&output.rect)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cursor, Output, Rect};
+
+    fn layout() -> Vec<Output> {
+        // Two outputs side by side with a gap between them.
+        vec![Output::new("left", Rect::new(0.0, 0.0, 100.0, 100.0)),
+             Output::new("right", Rect::new(200.0, 0.0, 100.0, 100.0))]
+    }
+
+    #[test]
+    fn clamp_stays_inside() {
+        let rect = Rect::new(10.0, 20.0, 1920.0, 1080.0);
+        assert_eq!(rect.clamp(500.0, 500.0), (500.0, 500.0));
+        assert_eq!(rect.clamp(-5.0, 0.0), (10.0, 20.0));
+
+        let (x, y) = rect.clamp(5000.0, 5000.0);
+        assert!(rect.contains(x, y));
+        assert!(1930.0 - x < 1e-9 && 1100.0 - y < 1e-9);
+
+        for &(x, y) in &[(1930.0, 50.0), (50.0, 1100.0), (1e12, -1e12)] {
+            let (x, y) = rect.clamp(x, y);
+            assert!(rect.contains(x, y), "{} {}", x, y);
+        }
+
+        let negative = Rect::new(-300.0, -200.0, 100.0, 100.0);
+        let (x, y) = negative.clamp(0.0, 0.0);
+        assert!(negative.contains(x, y));
+    }
+
+    #[test]
+    fn clamp_of_empty_rect_is_its_corner() {
+        assert_eq!(Rect::new(10.0, 20.0, 0.0, 0.0).clamp(50.0, 0.0), (10.0, 20.0));
+    }
+
+    #[test]
+    fn warp_snaps_out_of_gap_to_nearest_output() {
+        let mut cursor = Cursor::new(layout());
+
+        cursor.warp(120.0, 50.0);
+        let (x, y) = cursor.position();
+        assert!(x < 100.0 && 100.0 - x < 1e-9 && y == 50.0, "{} {}", x, y);
+        assert_eq!(cursor.output().map(|output| &output.name[..]), Some("left"));
+
+        cursor.warp(190.0, 50.0);
+        assert_eq!(cursor.position(), (200.0, 50.0));
+        assert_eq!(cursor.output().map(|output| &output.name[..]), Some("right"));
+
+        cursor.warp(250.0, -10.0);
+        assert_eq!(cursor.position(), (250.0, 0.0));
+
+        cursor.warp(1000.0, 1000.0);
+        assert_eq!(cursor.output().map(|output| &output.name[..]), Some("right"));
+    }
+
+    #[test]
+    fn output_under_cursor() {
+        let mut cursor = Cursor::new(layout());
+        assert_eq!(cursor.position(), (50.0, 50.0));
+        assert_eq!(cursor.output().map(|output| &output.name[..]), Some("left"));
+
+        cursor.warp(250.0, 99.0);
+        assert_eq!(cursor.output().map(|output| &output.name[..]), Some("right"));
+
+        // The layout shrinking takes the cursor along.
+        cursor.set_outputs(vec![Output::new("left", Rect::new(0.0, 0.0, 100.0, 100.0))]);
+        assert_eq!(cursor.output().map(|output| &output.name[..]), Some("left"));
+
+        cursor.set_outputs(Vec::new());
+        assert_eq!(cursor.output(), None);
+    }
+}
@@ -1,22 +1,158 @@
 use ::libinput_sys::*;
 use ::std::ffi::CStr;
+use ::std::fmt;
+use ::std::hash::{Hash, Hasher};
 use ::std::os::raw::c_char;
+use buttons::Button;
+use keys::Key;
+use time::Timestamp;
+use udev_device::UdevDevice;
+
+// The generated binding types the led mask as a single libinput_led, which
+// can't hold combined flags.
+extern "C" {
+    #[link_name = "libinput_device_led_update"]
+    fn libinput_device_led_update_mask(device: *mut libinput_device, leds: u32);
+}
+
+// Borrows a string owned by libinput. The caller picks a lifetime the
+// string is guaranteed to live for.
+unsafe fn cbuf_to_str<'a>(buf: *const c_char) -> &'a str {
+    ::std::str::from_utf8(CStr::from_ptr(buf).to_bytes()).unwrap_or("")
+}
+
+bitflags! {
+    /// Device capabilities.
+    pub struct Capabilities: u32 {
+        const KEYBOARD = 1 << 0;
+        const POINTER = 1 << 1;
+        const TOUCH = 1 << 2;
+        const TABLET_TOOL = 1 << 3;
+        const TABLET_PAD = 1 << 4;
+        const GESTURE = 1 << 5;
+        const SWITCH = 1 << 6;
+    }
+}
+
+#[cfg(feature = "serde")]
+const CAPABILITY_NAMES: [(Capabilities, &'static str); 7] = [
+    (Capabilities::KEYBOARD, "keyboard"),
+    (Capabilities::POINTER, "pointer"),
+    (Capabilities::TOUCH, "touch"),
+    (Capabilities::TABLET_TOOL, "tablet-tool"),
+    (Capabilities::TABLET_PAD, "tablet-pad"),
+    (Capabilities::GESTURE, "gesture"),
+    (Capabilities::SWITCH, "switch"),
+];
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Capabilities {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names: Vec<&str> = CAPABILITY_NAMES.iter()
+            .filter(|&&(flag, _)| self.contains(flag))
+            .map(|&(_, name)| name)
+            .collect();
+        ::serde::Serialize::serialize(&names, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Capabilities {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Capabilities, D::Error> {
+        use ::serde::de::Error;
+
+        let mut capabilities = Capabilities::empty();
+        let names: Vec<String> = ::serde::Deserialize::deserialize(deserializer)?;
+        for name in names {
+            match CAPABILITY_NAMES.iter().find(|&&(_, known)| known == name) {
+                Some(&(flag, _)) => capabilities |= flag,
+                None => return Err(D::Error::custom(format!("unknown capability {}", name))),
+            }
+        }
+        Ok(capabilities)
+    }
+}
 
-// Returns an allocated String.
-unsafe fn cbuf_to_string(buf: *const c_char) -> String {
-    let u8_buf = CStr::from_ptr(buf).to_bytes();
+const CAPABILITIES: [(Capabilities, libinput_device_capability); 7] = [
+    (Capabilities::KEYBOARD, libinput_device_capability::LIBINPUT_DEVICE_CAP_KEYBOARD),
+    (Capabilities::POINTER, libinput_device_capability::LIBINPUT_DEVICE_CAP_POINTER),
+    (Capabilities::TOUCH, libinput_device_capability::LIBINPUT_DEVICE_CAP_TOUCH),
+    (Capabilities::TABLET_TOOL, libinput_device_capability::LIBINPUT_DEVICE_CAP_TABLET_TOOL),
+    (Capabilities::TABLET_PAD, libinput_device_capability::LIBINPUT_DEVICE_CAP_TABLET_PAD),
+    (Capabilities::GESTURE, libinput_device_capability::LIBINPUT_DEVICE_CAP_GESTURE),
+    (Capabilities::SWITCH, libinput_device_capability::LIBINPUT_DEVICE_CAP_SWITCH),
+];
+
+bitflags! {
+    /// Keyboard lock LEDs. The values match libinput_led.
+    #[derive(Default)]
+    pub struct Leds: u32 {
+        const NUM_LOCK = 1 << 0;
+        const CAPS_LOCK = 1 << 1;
+        const SCROLL_LOCK = 1 << 2;
+    }
+}
 
-    // to_owned() allocates the buffer.
-    ::std::str::from_utf8(u8_buf).unwrap().to_owned()
+bitflags! {
+    /// Event classes, for filtering events before they are decoded.
+    pub struct EventClass: u32 {
+        /// Device added and removed.
+        const DEVICE = 1 << 0;
+        const KEYBOARD = 1 << 1;
+        const POINTER = 1 << 2;
+        const TOUCH = 1 << 3;
+        const TABLET_TOOL = 1 << 4;
+        const TABLET_PAD = 1 << 5;
+        const GESTURE = 1 << 6;
+        const SWITCH = 1 << 7;
+    }
+}
+
+impl EventClass {
+    pub(crate) fn from_event_type(event_type: libinput_event_type) -> EventClass {
+        use libinput_event_type::*;
+
+        match event_type {
+            LIBINPUT_EVENT_NONE => EventClass::empty(),
+            LIBINPUT_EVENT_DEVICE_ADDED
+            | LIBINPUT_EVENT_DEVICE_REMOVED => EventClass::DEVICE,
+            LIBINPUT_EVENT_KEYBOARD_KEY => EventClass::KEYBOARD,
+            LIBINPUT_EVENT_POINTER_MOTION
+            | LIBINPUT_EVENT_POINTER_MOTION_ABSOLUTE
+            | LIBINPUT_EVENT_POINTER_BUTTON
+            | LIBINPUT_EVENT_POINTER_AXIS => EventClass::POINTER,
+            LIBINPUT_EVENT_TOUCH_DOWN
+            | LIBINPUT_EVENT_TOUCH_UP
+            | LIBINPUT_EVENT_TOUCH_MOTION
+            | LIBINPUT_EVENT_TOUCH_CANCEL
+            | LIBINPUT_EVENT_TOUCH_FRAME => EventClass::TOUCH,
+            LIBINPUT_EVENT_TABLET_TOOL_AXIS
+            | LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY
+            | LIBINPUT_EVENT_TABLET_TOOL_TIP
+            | LIBINPUT_EVENT_TABLET_TOOL_BUTTON => EventClass::TABLET_TOOL,
+            LIBINPUT_EVENT_TABLET_PAD_BUTTON
+            | LIBINPUT_EVENT_TABLET_PAD_RING
+            | LIBINPUT_EVENT_TABLET_PAD_STRIP => EventClass::TABLET_PAD,
+            LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN
+            | LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE
+            | LIBINPUT_EVENT_GESTURE_SWIPE_END
+            | LIBINPUT_EVENT_GESTURE_PINCH_BEGIN
+            | LIBINPUT_EVENT_GESTURE_PINCH_UPDATE
+            | LIBINPUT_EVENT_GESTURE_PINCH_END => EventClass::GESTURE,
+            LIBINPUT_EVENT_SWITCH_TOGGLE => EventClass::SWITCH,
+        }
+    }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum State {
     Pressed,
     Released,
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Source {
     Wheel,
     Finger,
@@ -24,30 +160,86 @@ pub enum Source {
     WheelTilt,
 }
 
-pub struct Button {
-    state: State,
-    key: u32
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyboardEvent {
+    pub state: State,
+    pub key: Key,
+    /// Number of keyboards on the seat holding this key, after this event.
+    pub seat_key_count: u32,
 }
 
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ButtonEvent {
+    pub state: State,
+    pub button: Button,
+    /// Number of devices on the seat holding this button, after this event.
+    pub seat_button_count: u32,
+}
+
+/// Relative pointer motion.
+/// The unaccelerated deltas are in the same units as the accelerated ones, but
+/// without any pointer acceleration applied.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Motion {
-    x: f64,
-    y: f64
+    pub dx: f64,
+    pub dy: f64,
+    pub dx_unaccelerated: f64,
+    pub dy_unaccelerated: f64,
 }
 
+/// Absolute pointer position in mm from the top left corner of the device.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Scroll event. Axes that did not change in this event are None.
+/// The discrete values count physical wheel clicks and are only present for
+/// Source::Wheel.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Axis {
+    pub source: Source,
+    pub vertical: Option<f64>,
+    pub horizontal: Option<f64>,
+    pub vertical_discrete: Option<f64>,
+    pub horizontal_discrete: Option<f64>,
+}
+
+/// A touch point. The slot identifies the touch on its device until it is
+/// lifted, the seat slot across all devices of the seat. The position is in
+/// mm from the top left corner of the device.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Touch {
+    pub slot: i32,
+    pub seat_slot: i32,
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EventType {
     None,
     DeviceAdd,
     DeviceRemove,
-    Keyboard(Button),
+    Keyboard(KeyboardEvent),
     MouseMove(Motion),
-    MouseMoveAbsolute(Motion)
-    MouseButton(Button)
-    MouseAxis(Source, Option<f64>, Option<f64>),
-    TouchDown(f64, f64),
-    TouchMotion(f64, f64),
-    TouchUp,
-    TouchCancel,
+    MouseMoveAbsolute(Position),
+    MouseButton(ButtonEvent),
+    MouseAxis(Axis),
+    TouchDown(Touch),
+    TouchMotion(Touch),
+    /// Slot of the lifted touch.
+    TouchUp(i32),
+    /// Slot of the cancelled touch.
+    TouchCancel(i32),
     TouchFrame,
     GestureSwipeBegin(u8),
     GestureSwipeUpdate(u8, f64, f64, f64, f64),
@@ -58,15 +250,19 @@ pub enum EventType {
     TabletAxis,
     TabletProximity,
     TabletTip,
-    TabletButton,
+    TabletButton(ButtonEvent),
     TabletpadButton,
     TabletpadRing,
     TabletpadStrip,
+    SwitchToggle,
 }
 pub struct Event {
     lib_handle: *mut libinput_event,
     device: Device,
     event_type: EventType,
+    time: Option<Timestamp>,
+    // Reference point for the relative time printed by Display.
+    start: Timestamp,
 }
 
 impl Event {
@@ -78,37 +274,146 @@ impl Event {
         &self.device
     }
 
+    pub fn record(&self) -> EventRecord {
+        EventRecord {
+            time: self.time,
+            device: self.device.info(),
+            event: self.event_type,
+        }
+    }
+
+    pub fn class(&self) -> EventClass {
+        EventClass::from_event_type(unsafe { libinput_event_get_type(self.lib_handle) })
+    }
+
+    /// Time of the event in milliseconds, 0 for device added/removed events.
     pub fn time(&self) -> u64 {
-        self.time_usec() / 1000
+        self.time.map_or(0, |time| time.as_msec())
     }
 
+    /// Time of the event in microseconds, 0 for device added/removed events.
     pub fn time_usec(&self) -> u64 {
-        match self.event_type {
-            EventType::KeyboardInput(_, _) => unsafe {
-                let key_event = libinput_event_get_keyboard_event(self.lib_handle);
-                libinput_event_keyboard_get_time_usec(key_event)
-            },
-            EventType::MouseMove(_, _)
-            | EventType::MouseMoveAbsolute(_, _)
-            | EventType::MouseButton(_, _)
-            | EventType::MouseAxis(_, _, _) => unsafe {
-                let mouse_event = libinput_event_get_pointer_event(self.lib_handle);
-                libinput_event_pointer_get_time_usec(mouse_event)
-            },
-            _ => 0,
+        self.time.map_or(0, |time| time.as_usec())
+    }
+
+    /// Absolute position scaled to a `width` x `height` area, e.g. a screen.
+    /// Available for absolute pointer motion, touch down/motion and tablet tool
+    /// events; None for everything else.
+    pub fn transformed_position(&self, width: u32, height: u32) -> Option<(f64, f64)> {
+        use libinput_event_type::*;
+
+        unsafe {
+            match libinput_event_get_type(self.lib_handle) {
+                LIBINPUT_EVENT_POINTER_MOTION_ABSOLUTE => {
+                    let mouse_event = libinput_event_get_pointer_event(self.lib_handle);
+                    Some((libinput_event_pointer_get_absolute_x_transformed(mouse_event, width),
+                          libinput_event_pointer_get_absolute_y_transformed(mouse_event, height)))
+                },
+                LIBINPUT_EVENT_TOUCH_DOWN
+                | LIBINPUT_EVENT_TOUCH_MOTION => {
+                    let touch_event = libinput_event_get_touch_event(self.lib_handle);
+                    Some((libinput_event_touch_get_x_transformed(touch_event, width),
+                          libinput_event_touch_get_y_transformed(touch_event, height)))
+                },
+                LIBINPUT_EVENT_TABLET_TOOL_AXIS
+                | LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY
+                | LIBINPUT_EVENT_TABLET_TOOL_TIP
+                | LIBINPUT_EVENT_TABLET_TOOL_BUTTON => {
+                    let tablet_event = libinput_event_get_tablet_tool_event(self.lib_handle);
+                    Some((libinput_event_tablet_tool_get_x_transformed(tablet_event, width),
+                          libinput_event_tablet_tool_get_y_transformed(tablet_event, height)))
+                },
+                _ => None,
+            }
         }
     }
+
+    pub(crate) fn as_raw(&self) -> *mut libinput_event {
+        self.lib_handle
+    }
+
+    pub(crate) fn start(&self) -> Timestamp {
+        self.start
+    }
+
+    pub(crate) fn set_start(&mut self, start: Timestamp) {
+        self.start = start;
+    }
+
+    /// Time of the event on the CLOCK_MONOTONIC clock.
+    /// None for device added/removed events, libinput does not timestamp them.
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        self.time
+    }
+}
+
+unsafe fn touch_point(touch_event: *mut libinput_event_touch) -> Touch {
+    Touch {
+        slot: libinput_event_touch_get_slot(touch_event),
+        seat_slot: libinput_event_touch_get_seat_slot(touch_event),
+        x: libinput_event_touch_get_x(touch_event),
+        y: libinput_event_touch_get_y(touch_event),
+    }
+}
+
+// Reads the timestamp from whichever event class this event belongs to.
+unsafe fn event_time(event_handle: *mut libinput_event, event_type: libinput_event_type) -> Option<Timestamp> {
+    use libinput_event_type::*;
+
+    let usec = match event_type {
+        LIBINPUT_EVENT_NONE
+        | LIBINPUT_EVENT_DEVICE_ADDED
+        | LIBINPUT_EVENT_DEVICE_REMOVED => return None,
+        LIBINPUT_EVENT_KEYBOARD_KEY => {
+            libinput_event_keyboard_get_time_usec(libinput_event_get_keyboard_event(event_handle))
+        },
+        LIBINPUT_EVENT_POINTER_MOTION
+        | LIBINPUT_EVENT_POINTER_MOTION_ABSOLUTE
+        | LIBINPUT_EVENT_POINTER_BUTTON
+        | LIBINPUT_EVENT_POINTER_AXIS => {
+            libinput_event_pointer_get_time_usec(libinput_event_get_pointer_event(event_handle))
+        },
+        LIBINPUT_EVENT_TOUCH_DOWN
+        | LIBINPUT_EVENT_TOUCH_UP
+        | LIBINPUT_EVENT_TOUCH_MOTION
+        | LIBINPUT_EVENT_TOUCH_CANCEL
+        | LIBINPUT_EVENT_TOUCH_FRAME => {
+            libinput_event_touch_get_time_usec(libinput_event_get_touch_event(event_handle))
+        },
+        LIBINPUT_EVENT_TABLET_TOOL_AXIS
+        | LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY
+        | LIBINPUT_EVENT_TABLET_TOOL_TIP
+        | LIBINPUT_EVENT_TABLET_TOOL_BUTTON => {
+            libinput_event_tablet_tool_get_time_usec(libinput_event_get_tablet_tool_event(event_handle))
+        },
+        LIBINPUT_EVENT_TABLET_PAD_BUTTON
+        | LIBINPUT_EVENT_TABLET_PAD_RING
+        | LIBINPUT_EVENT_TABLET_PAD_STRIP => {
+            libinput_event_tablet_pad_get_time_usec(libinput_event_get_tablet_pad_event(event_handle))
+        },
+        LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN
+        | LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE
+        | LIBINPUT_EVENT_GESTURE_SWIPE_END
+        | LIBINPUT_EVENT_GESTURE_PINCH_BEGIN
+        | LIBINPUT_EVENT_GESTURE_PINCH_UPDATE
+        | LIBINPUT_EVENT_GESTURE_PINCH_END => {
+            libinput_event_gesture_get_time_usec(libinput_event_get_gesture_event(event_handle))
+        },
+        LIBINPUT_EVENT_SWITCH_TOGGLE => {
+            libinput_event_switch_get_time_usec(libinput_event_get_switch_event(event_handle))
+        },
+    };
+
+    Some(Timestamp::from_usec(usec))
 }
 
 impl From<*mut libinput_event> for Event {
     fn from(event_handle: *mut libinput_event) -> Self {
         let libevent_type = unsafe { libinput_event_get_type(event_handle) };
         let device = Device::from(event_handle);
+        let time = unsafe { event_time(event_handle, libevent_type) };
         let event_type = match libevent_type {
-            libinput_event_type::LIBINPUT_EVENT_NONE => {
-                unsafe { abort() };
-                EventType::None
-            },
+            libinput_event_type::LIBINPUT_EVENT_NONE => ::std::process::abort(),
             libinput_event_type::LIBINPUT_EVENT_DEVICE_ADDED => {
                 EventType::DeviceAdd
             },
@@ -125,21 +430,33 @@ impl From<*mut libinput_event> for Event {
                     }
                 };
 
-                EventType::KeyboardInput(key_state, key)
+                let seat_key_count = unsafe { libinput_event_keyboard_get_seat_key_count(key_event) };
+
+                EventType::Keyboard(KeyboardEvent {
+                    state: key_state,
+                    key: Key::from_code(key),
+                    seat_key_count: seat_key_count,
+                })
             },
             libinput_event_type::LIBINPUT_EVENT_POINTER_MOTION => {
                 let mouse_event = unsafe { libinput_event_get_pointer_event(event_handle) };
-                let x = unsafe { libinput_event_pointer_get_dx(mouse_event) };
-                let y = unsafe { libinput_event_pointer_get_dy(mouse_event) };
+                let motion = unsafe {
+                    Motion {
+                        dx: libinput_event_pointer_get_dx(mouse_event),
+                        dy: libinput_event_pointer_get_dy(mouse_event),
+                        dx_unaccelerated: libinput_event_pointer_get_dx_unaccelerated(mouse_event),
+                        dy_unaccelerated: libinput_event_pointer_get_dy_unaccelerated(mouse_event),
+                    }
+                };
 
-                EventType::MouseMove(x, y)
+                EventType::MouseMove(motion)
             },
             libinput_event_type::LIBINPUT_EVENT_POINTER_MOTION_ABSOLUTE => {
                 let mouse_event = unsafe { libinput_event_get_pointer_event(event_handle) };
                 let x = unsafe { libinput_event_pointer_get_absolute_x(mouse_event) };
                 let y = unsafe { libinput_event_pointer_get_absolute_y(mouse_event) };
 
-                EventType::MouseMoveAbsolute(x, y)
+                EventType::MouseMoveAbsolute(Position { x: x, y: y })
             },
             libinput_event_type::LIBINPUT_EVENT_POINTER_BUTTON => {
                 let mouse_event = unsafe { libinput_event_get_pointer_event(event_handle) };
@@ -151,10 +468,17 @@ impl From<*mut libinput_event> for Event {
                     }
                 };
 
-                EventType::MouseButton(button_state, button)
+                let seat_button_count = unsafe { libinput_event_pointer_get_seat_button_count(mouse_event) };
+
+                EventType::MouseButton(ButtonEvent {
+                    state: button_state,
+                    button: Button::from_code(button),
+                    seat_button_count: seat_button_count,
+                })
             },
             libinput_event_type::LIBINPUT_EVENT_POINTER_AXIS => {
                 use libinput_pointer_axis::*;
+                use libinput_pointer_axis_source::*;
 
                 let mouse_event = unsafe { libinput_event_get_pointer_event(event_handle) };
                 let source_type = {
@@ -166,81 +490,87 @@ impl From<*mut libinput_event> for Event {
                     }
                 };
 
-                let vert = unsafe {
-                    if libinput_event_pointer_has_axis(mouse_event, LIBINPUT_POINTER_AXIS_SCROLL_VERTICAL) != 0 {
-                        Some(libinput_event_pointer_get_axis_value(mouse_event, LIBINPUT_POINTER_AXIS_SCROLL_VERTICAL))
+                let axis_value = |axis| unsafe {
+                    if libinput_event_pointer_has_axis(mouse_event, axis) != 0 {
+                        Some(libinput_event_pointer_get_axis_value(mouse_event, axis))
                     }
                     else {
                         None
                     }
                 };
 
-                let hori = unsafe {
-                    if libinput_event_pointer_has_axis(mouse_event, LIBINPUT_POINTER_AXIS_SCROLL_HORIZONTAL) != 0 {
-                        Some(libinput_event_pointer_get_axis_value(mouse_event, LIBINPUT_POINTER_AXIS_SCROLL_HORIZONTAL))
-                    }
-                    else {
-                        None
+                // Discrete values are only meaningful for wheel sources, libinput
+                // returns 0 for everything else.
+                let axis_discrete = |axis| unsafe {
+                    match source_type {
+                        Source::Wheel if libinput_event_pointer_has_axis(mouse_event, axis) != 0 => {
+                            Some(libinput_event_pointer_get_axis_value_discrete(mouse_event, axis))
+                        },
+                        _ => None,
                     }
                 };
 
-                EventType::MouseAxis(source_type, vert, hori)
+                EventType::MouseAxis(Axis {
+                    source: source_type,
+                    vertical: axis_value(LIBINPUT_POINTER_AXIS_SCROLL_VERTICAL),
+                    horizontal: axis_value(LIBINPUT_POINTER_AXIS_SCROLL_HORIZONTAL),
+                    vertical_discrete: axis_discrete(LIBINPUT_POINTER_AXIS_SCROLL_VERTICAL),
+                    horizontal_discrete: axis_discrete(LIBINPUT_POINTER_AXIS_SCROLL_HORIZONTAL),
+                })
             },
-            libinput_event_type::LIBINPUT_TOUCH_DOWN => {
+            libinput_event_type::LIBINPUT_EVENT_TOUCH_DOWN => {
                 unsafe {
                     let touch_event = libinput_event_get_touch_event(event_handle);
-                    //let x = libinput_event_touch_get_x_transformed(t, screen_width);
-                    //let y = libinput_event_touch_get_y_transformed(t, screen_height);
-                    let x = libinput_event_touch_get_x(touch_event);
-                    let y = libinput_event_touch_get_y(touch_event);
-
-                    EventType::TouchDown(x, y)
+                    EventType::TouchDown(touch_point(touch_event))
                 }
             },
-            libinput_event_type::LIBINPUT_TOUCH_MOTION => {
+            libinput_event_type::LIBINPUT_EVENT_TOUCH_MOTION => {
                 unsafe {
                     let touch_event = libinput_event_get_touch_event(event_handle);
-                    let x = libinput_event_touch_get_x(touch_event);
-                    let y = libinput_event_touch_get_y(touch_event);
-
-                    EventType::TouchMotion(x, y)
+                    EventType::TouchMotion(touch_point(touch_event))
                 }
             },
-            libinput_event_type::LIBINPUT_TOUCH_UP => EventType::TouchUp,
-            libinput_event_type::LIBINPUT_TOUCH_CANCEL => EventType::TouchCancel,
-            libinput_event_type::LIBINPUT_TOUCH_FRAME => EventType::TouchFrame,
-	        libinput_event_type::LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN => {
+            libinput_event_type::LIBINPUT_EVENT_TOUCH_UP => {
+                let touch_event = unsafe { libinput_event_get_touch_event(event_handle) };
+                EventType::TouchUp(unsafe { libinput_event_touch_get_slot(touch_event) })
+            },
+            libinput_event_type::LIBINPUT_EVENT_TOUCH_CANCEL => {
+                let touch_event = unsafe { libinput_event_get_touch_event(event_handle) };
+                EventType::TouchCancel(unsafe { libinput_event_touch_get_slot(touch_event) })
+            },
+            libinput_event_type::LIBINPUT_EVENT_TOUCH_FRAME => EventType::TouchFrame,
+            libinput_event_type::LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN => {
                 let gesture_event = unsafe { libinput_event_get_gesture_event(event_handle) };
                 let fingers = unsafe { libinput_event_gesture_get_finger_count(gesture_event) };
-                EventType::GestureSwipeBegin(fingers)
+                EventType::GestureSwipeBegin(fingers as u8)
             },
-	        libinput_event_type::LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE => {
+            libinput_event_type::LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE => {
                 unsafe {
                     let gesture_event = libinput_event_get_gesture_event(event_handle);
-                    let fingers = unsafe { libinput_event_gesture_get_finger_count(gesture_event) };
+                    let fingers = libinput_event_gesture_get_finger_count(gesture_event);
                     let dx = libinput_event_gesture_get_dx(gesture_event);
                     let dy = libinput_event_gesture_get_dy(gesture_event);
                     let dx_unaccel = libinput_event_gesture_get_dx_unaccelerated(gesture_event);
                     let dy_unaccel = libinput_event_gesture_get_dy_unaccelerated(gesture_event);
 
-                    EventType::GestureSwipeUpdate(fingers, dx, dy, dx_unaccel, dy_unaccel)
+                    EventType::GestureSwipeUpdate(fingers as u8, dx, dy, dx_unaccel, dy_unaccel)
                 }
             },
-	        libinput_event_type::LIBINPUT_EVENT_GESTURE_SWIPE_END => {
+            libinput_event_type::LIBINPUT_EVENT_GESTURE_SWIPE_END => {
                 let gesture_event = unsafe { libinput_event_get_gesture_event(event_handle) };
                 let fingers = unsafe { libinput_event_gesture_get_finger_count(gesture_event) };
                 let cancelled = unsafe { libinput_event_gesture_get_cancelled(gesture_event) };
-                EventType::GestureSwipeEnd(fingers, cancelled)
+                EventType::GestureSwipeEnd(fingers as u8, cancelled != 0)
             },
-	        libinput_event_type::LIBINPUT_EVENT_GESTURE_PINCH_BEGIN => {
+            libinput_event_type::LIBINPUT_EVENT_GESTURE_PINCH_BEGIN => {
                 let gesture_event = unsafe { libinput_event_get_gesture_event(event_handle) };
                 let fingers = unsafe { libinput_event_gesture_get_finger_count(gesture_event) };
-                EventType::GesturePinchEnd(fingers)
+                EventType::GesturePinchBegin(fingers as u8)
             },
-	        libinput_event_type::LIBINPUT_EVENT_GESTURE_PINCH_UPDATE => {
+            libinput_event_type::LIBINPUT_EVENT_GESTURE_PINCH_UPDATE => {
                 unsafe {
                     let gesture_event = libinput_event_get_gesture_event(event_handle);
-                    let fingers = unsafe { libinput_event_gesture_get_finger_count(gesture_event) };
+                    let fingers = libinput_event_gesture_get_finger_count(gesture_event);
                     let dx = libinput_event_gesture_get_dx(gesture_event);
                     let dy = libinput_event_gesture_get_dy(gesture_event);
                     let dx_unaccel = libinput_event_gesture_get_dx_unaccelerated(gesture_event);
@@ -248,80 +578,48 @@ impl From<*mut libinput_event> for Event {
                     let scale = libinput_event_gesture_get_scale(gesture_event);
                     let angle = libinput_event_gesture_get_angle_delta(gesture_event);
 
-                    EventType::GestureSwipeUpdate(fingers, dx, dy, dx_unaccel, dy_unaccel, scale, angle)
+                    EventType::GesturePinchUpdate(fingers as u8, dx, dy, dx_unaccel, dy_unaccel, scale, angle)
                 }
             },
-            fn tablet_event_axes(libinput_event_tablet_tool) {
-                unsafe {
-                    let tool = libinput_event_tablet_tool_get_tool(tablet_event);
-                    let x = libinput_event_tablet_tool_get_x(t);
-                    let y = libinput_event_tablet_tool_get_y(t);
-
-                    if libinput_tablet_tool_has_tilt(tool) {
-                        x = libinput_event_tablet_tool_get_tilt_x(t);
-                        y = libinput_event_tablet_tool_get_tilt_y(t);
-                        printq("\ttilt: %.2f%s/%.2f%s",
-                               x, changed_sym(t, tilt_x),
-                               y, changed_sym(t, tilt_y));
-                    }
-
-                    if (libinput_tablet_tool_has_distance(tool) ||
-                        libinput_tablet_tool_has_pressure(tool)) {
-                        dist = libinput_event_tablet_tool_get_distance(t);
-                        pressure = libinput_event_tablet_tool_get_pressure(t);
-                        if (dist)
-                            printq("\tdistance: %.2f%s",
-                                   dist, changed_sym(t, distance));
-                        else
-                            printq("\tpressure: %.2f%s",
-                                   pressure, changed_sym(t, pressure));
-                    }
-
-                    if (libinput_tablet_tool_has_rotation(tool)) {
-                        rotation = libinput_event_tablet_tool_get_rotation(t);
-                        printq("\trotation: %.2f%s",
-                               rotation, changed_sym(t, rotation));
-                    }
-
-                    if (libinput_tablet_tool_has_slider(tool)) {
-                        slider = libinput_event_tablet_tool_get_slider_position(t);
-                        printq("\tslider: %.2f%s",
-                               slider, changed_sym(t, slider));
-                    }
-
-                    if (libinput_tablet_tool_has_wheel(tool)) {
-                        wheel = libinput_event_tablet_tool_get_wheel_delta(t);
-                        delta = libinput_event_tablet_tool_get_wheel_delta_discrete(t);
-                        printq("\twheel: %.2f%s (%d)",
-                               wheel, changed_sym(t, wheel),
-                               (int)delta);
-                    }
-                }
-            }
-	        libinput_event_type::LIBINPUT_EVENT_GESTURE_PINCH_END => {
+            libinput_event_type::LIBINPUT_EVENT_GESTURE_PINCH_END => {
                 let gesture_event = unsafe { libinput_event_get_gesture_event(event_handle) };
                 let fingers = unsafe { libinput_event_gesture_get_finger_count(gesture_event) };
                 let cancelled = unsafe { libinput_event_gesture_get_cancelled(gesture_event) };
-                EventType::GesturePinchEnd(fingers, cancelled)
+                EventType::GesturePinchEnd(fingers as u8, cancelled != 0)
             },
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_AXIS => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_TIP => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_BUTTON => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_PAD_BUTTON => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_PAD_RING => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_TABLET_PAD_STRIP => EventType::Gesture,
-	        libinput_event_type::LIBINPUT_EVENT_SWITCH_TOGGLE => EventType::Gesture,
-            _ => {
-                println!("Event type unimplemented.");
-                EventType::None
+            libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_AXIS => EventType::TabletAxis,
+            libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY => EventType::TabletProximity,
+            libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_TIP => EventType::TabletTip,
+            libinput_event_type::LIBINPUT_EVENT_TABLET_TOOL_BUTTON => {
+                let tablet_event = unsafe { libinput_event_get_tablet_tool_event(event_handle) };
+                let button = unsafe { libinput_event_tablet_tool_get_button(tablet_event) };
+                let button_state = {
+                    match unsafe { libinput_event_tablet_tool_get_button_state(tablet_event) } {
+                        libinput_button_state::LIBINPUT_BUTTON_STATE_PRESSED => State::Pressed,
+                        libinput_button_state::LIBINPUT_BUTTON_STATE_RELEASED => State::Released,
+                    }
+                };
+
+                let seat_button_count = unsafe { libinput_event_tablet_tool_get_seat_button_count(tablet_event) };
+
+                EventType::TabletButton(ButtonEvent {
+                    state: button_state,
+                    button: Button::from_code(button),
+                    seat_button_count: seat_button_count,
+                })
             },
+            libinput_event_type::LIBINPUT_EVENT_TABLET_PAD_BUTTON => EventType::TabletpadButton,
+            libinput_event_type::LIBINPUT_EVENT_TABLET_PAD_RING => EventType::TabletpadRing,
+            libinput_event_type::LIBINPUT_EVENT_TABLET_PAD_STRIP => EventType::TabletpadStrip,
+            libinput_event_type::LIBINPUT_EVENT_SWITCH_TOGGLE => EventType::SwitchToggle,
         };
 
         Event {
             lib_handle: event_handle,
             device: device,
-            event_type: event_type
+            event_type: event_type,
+            time: time,
+            start: time.unwrap_or(Timestamp::from_usec(0)),
         }
     }
 }
@@ -332,41 +630,271 @@ impl Drop for Event {
     }
 }
 
+/// An event detached from libinput, e.g. for logging or test fixtures.
+///
+/// With the serde feature, records serialize with the field names below. The
+/// event uses serde's default enum layout: unit variants as a string
+/// ("TouchFrame"), the others as a single key object named after the variant
+/// holding the payload, tuple payloads as arrays in declaration order. Keys
+/// and buttons are kernel names ("KEY_A", "BTN_LEFT"), or hex codes for codes
+/// without a name. Timestamps are CLOCK_MONOTONIC microseconds, or null for
+/// device events, capabilities a list of lowercase names such as "keyboard"
+/// or "tablet-tool".
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EventRecord {
+    pub time: Option<Timestamp>,
+    pub device: DeviceInfo,
+    pub event: EventType,
+}
+
+/// Identity of a device, detached from libinput.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceInfo {
+    pub name: String,
+    pub sysname: String,
+    pub id_vendor: u32,
+    pub id_product: u32,
+    pub physical_seat: String,
+    pub logical_seat: String,
+    pub capabilities: Capabilities,
+}
+
+/// A libinput device. Holds a reference on the underlying device and on the
+/// libinput context, so it stays valid after the event it came from, or the
+/// LibInput it was read from, is dropped.
+///
+/// Device is not Send: libinput reference counts are not atomic, so devices
+/// must stay on the thread that reads the events.
 pub struct Device {
-    name: String,
-    physical_seat: String,
-    logical_seat: String,
+    handle: *mut libinput_device,
 }
 
 impl Device {
     pub fn name(&self) -> &str {
-        &self.name
+        unsafe { cbuf_to_str(libinput_device_get_name(self.handle)) }
     }
 
     pub fn physical_seat(&self) -> &str {
-        &self.physical_seat
+        unsafe { cbuf_to_str(libinput_seat_get_physical_name(libinput_device_get_seat(self.handle))) }
     }
 
     pub fn logical_seat(&self) -> &str {
-        &self.logical_seat
+        unsafe { cbuf_to_str(libinput_seat_get_logical_name(libinput_device_get_seat(self.handle))) }
+    }
+
+    /// Kernel name of the event node, e.g. "event3".
+    pub fn sysname(&self) -> &str {
+        unsafe { cbuf_to_str(libinput_device_get_sysname(self.handle)) }
+    }
+
+    /// USB or bluetooth vendor id, 0 if unknown.
+    pub fn id_vendor(&self) -> u32 {
+        unsafe { libinput_device_get_id_vendor(self.handle) }
+    }
+
+    /// USB or bluetooth product id, 0 if unknown.
+    pub fn id_product(&self) -> u32 {
+        unsafe { libinput_device_get_id_product(self.handle) }
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        CAPABILITIES.iter()
+            .filter(|&&(_, capability)| unsafe { libinput_device_has_capability(self.handle, capability) != 0 })
+            .fold(Capabilities::empty(), |capabilities, &(flag, _)| capabilities | flag)
+    }
+
+    /// Owned snapshot of the device identity.
+    pub fn info(&self) -> DeviceInfo {
+        DeviceInfo {
+            name: self.name().to_owned(),
+            sysname: self.sysname().to_owned(),
+            id_vendor: self.id_vendor(),
+            id_product: self.id_product(),
+            physical_seat: self.physical_seat().to_owned(),
+            logical_seat: self.logical_seat().to_owned(),
+            capabilities: self.capabilities(),
+        }
+    }
+
+    /// Whether the device has all of the given capabilities.
+    pub fn has_capability(&self, capabilities: Capabilities) -> bool {
+        CAPABILITIES.iter()
+            .filter(|&&(flag, _)| capabilities.contains(flag))
+            .all(|&(_, capability)| unsafe { libinput_device_has_capability(self.handle, capability) != 0 })
+    }
+
+    /// Output the device is attached to, e.g. the screen of a touchscreen, as
+    /// set by the WL_OUTPUT udev property.
+    pub fn output_name(&self) -> Option<&str> {
+        unsafe {
+            let output_name = libinput_device_get_output_name(self.handle);
+            if output_name.is_null() {
+                None
+            }
+            else {
+                Some(cbuf_to_str(output_name))
+            }
+        }
+    }
+
+    /// Physical size of the device in mm, for absolute devices.
+    pub fn size(&self) -> Option<(f64, f64)> {
+        let mut width = 0_f64;
+        let mut height = 0_f64;
+
+        match unsafe { libinput_device_get_size(self.handle, &mut width, &mut height) } {
+            0 => Some((width, height)),
+            _ => None,
+        }
+    }
+
+    /// Whether the device has the given button. False for non-pointer devices.
+    pub fn pointer_has_button(&self, button: Button) -> bool {
+        unsafe { libinput_device_pointer_has_button(self.handle, button.code()) > 0 }
+    }
+
+    /// Whether the device has the given key. False for non-keyboard devices.
+    pub fn keyboard_has_key(&self, key: Key) -> bool {
+        unsafe { libinput_device_keyboard_has_key(self.handle, key.code()) > 0 }
+    }
+
+    /// Turns the given LEDs on and all others off. Ignored by devices
+    /// without LEDs.
+    pub fn set_leds(&self, leds: Leds) {
+        unsafe { libinput_device_led_update_mask(self.handle, leds.bits()) };
+    }
+
+    /// The udev device. None for devices not created from udev.
+    pub fn udev_device(&self) -> Option<UdevDevice> {
+        unsafe { UdevDevice::from_raw(libinput_device_get_udev_device(self.handle)) }
+    }
+}
+
+/// Devices compare equal if they refer to the same libinput device.
+impl PartialEq for Device {
+    fn eq(&self, other: &Device) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for Device {}
+
+impl Hash for Device {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle.hash(state)
+    }
+}
+
+impl Clone for Device {
+    fn clone(&self) -> Device {
+        unsafe {
+            libinput_ref(libinput_device_get_context(self.handle));
+            libinput_device_ref(self.handle);
+        }
+
+        Device { handle: self.handle }
+    }
+}
+
+impl fmt::Debug for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Device")
+            .field("name", &self.name())
+            .field("physical_seat", &self.physical_seat())
+            .field("logical_seat", &self.logical_seat())
+            .finish()
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        unsafe {
+            // The device lives in the context, release it first.
+            let context = libinput_device_get_context(self.handle);
+            libinput_device_unref(self.handle);
+            libinput_unref(context);
+        }
+    }
+}
+
+impl Device {
+    pub(crate) fn as_raw(&self) -> *mut libinput_device {
+        self.handle
     }
 }
 
 impl From<*mut libinput_event> for Device {
     fn from(event: *mut libinput_event) -> Device {
         unsafe {
-            let device = libinput_event_get_device(event);
-            let seat = libinput_device_get_seat(device);
-            let dev_name = libinput_device_get_name(device);
-            let phys_seat = libinput_seat_get_physical_name(seat);
-            let log_seat = libinput_seat_get_logical_name(seat);
-
-            return Device {
-                name: cbuf_to_string(dev_name),
-                physical_seat: cbuf_to_string(phys_seat),
-                logical_seat: cbuf_to_string(log_seat),
-            }
+            let device = libinput_device_ref(libinput_event_get_device(event));
+            libinput_ref(libinput_device_get_context(device));
+
+            return Device { handle: device }
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use ::serde_json;
+    use keys::Key;
+    use time::Timestamp;
+    use super::{Capabilities, DeviceInfo, EventRecord, EventType, KeyboardEvent, State};
+
+    const DEVICE_JSON: &'static str = "{\"name\":\"Tablet\",\"sysname\":\"event5\",\"id_vendor\":1386,\"id_product\":890,\
+                                       \"physical_seat\":\"seat0\",\"logical_seat\":\"default\",\
+                                       \"capabilities\":[\"keyboard\",\"tablet-tool\"]}";
+
+    fn record(time: Option<u64>, event: EventType) -> EventRecord {
+        EventRecord {
+            time: time.map(Timestamp::from_usec),
+            device: DeviceInfo {
+                name: "Tablet".to_owned(),
+                sysname: "event5".to_owned(),
+                id_vendor: 0x56a,
+                id_product: 0x37a,
+                physical_seat: "seat0".to_owned(),
+                logical_seat: "default".to_owned(),
+                capabilities: Capabilities::KEYBOARD | Capabilities::TABLET_TOOL,
+            },
+            event: event,
+        }
+    }
+
+    // Checks the exact JSON of the record, and that it reads back unchanged.
+    fn assert_json(record: EventRecord, event_json: &str, time_json: &str) {
+        let json = format!("{{\"time\":{},\"device\":{},\"event\":{}}}", time_json, DEVICE_JSON, event_json);
+        assert_eq!(serde_json::to_string(&record).unwrap(), json);
+
+        let parsed: EventRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.device, record.device);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+
+    fn key(key: Key) -> EventType {
+        EventType::Keyboard(KeyboardEvent { state: State::Pressed, key: key, seat_key_count: 1 })
+    }
+
+    #[test]
+    fn keys_are_kernel_names_or_hex() {
+        assert_json(record(Some(1234567), key(Key::A)),
+                    "{\"Keyboard\":{\"state\":\"Pressed\",\"key\":\"KEY_A\",\"seat_key_count\":1}}",
+                    "1234567");
+        assert_json(record(Some(1234567), key(Key::from_code(0x2ff))),
+                    "{\"Keyboard\":{\"state\":\"Pressed\",\"key\":\"0x2ff\",\"seat_key_count\":1}}",
+                    "1234567");
+    }
+
+    #[test]
+    fn device_events_have_no_time() {
+        assert_json(record(None, EventType::DeviceAdd), "\"DeviceAdd\"", "null");
+    }
+
+    #[test]
+    fn tuple_variants_are_arrays() {
+        assert_json(record(Some(42), EventType::GestureSwipeEnd(3, false)), "{\"GestureSwipeEnd\":[3,false]}", "42");
+        assert_json(record(Some(42), EventType::TouchUp(1)), "{\"TouchUp\":1}", "42");
+    }
+}
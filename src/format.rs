@@ -0,0 +1,291 @@
+//! Display impls printing events and devices like `libinput debug-events`.
+
+use ::libinput_sys::*;
+use ::std::fmt;
+use ::std::os::raw::c_int;
+use events::{Capabilities, Device, Event, EventType, Source, State};
+
+// debug-events scales absolute coordinates to a 100x100 screen.
+const SCREEN_SIZE: u32 = 100;
+
+/// Prints the device like the DEVICE_ADDED line of debug-events: name, seats,
+/// capability letters and the size of absolute devices.
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<33} {:>5} {:>7} cap:", self.name(), self.physical_seat(), self.logical_seat())?;
+
+        let letters = [
+            (Capabilities::KEYBOARD, 'k'),
+            (Capabilities::POINTER, 'p'),
+            (Capabilities::TOUCH, 't'),
+            (Capabilities::GESTURE, 'g'),
+            (Capabilities::TABLET_TOOL, 'T'),
+            (Capabilities::TABLET_PAD, 'P'),
+            (Capabilities::SWITCH, 'S'),
+        ];
+        let capabilities = self.capabilities();
+        for &(capability, letter) in &letters {
+            if capabilities.contains(capability) {
+                write!(f, "{}", letter)?;
+            }
+        }
+
+        if let Some((width, height)) = self.size() {
+            write!(f, "  size {:.0}x{:.0}mm", width, height)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the event like a line of debug-events: the sysname, the event type,
+/// the time in seconds since the first event read from the context and the
+/// details of the event. Changed tablet axes and scroll axes are marked with
+/// a `*`. Device events are marked with a leading `-` and print the device
+/// instead of a time.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use libinput_event_type::*;
+
+        let event_type = unsafe { libinput_event_get_type(self.as_raw()) };
+        let prefix = match event_type {
+            LIBINPUT_EVENT_DEVICE_ADDED | LIBINPUT_EVENT_DEVICE_REMOVED => '-',
+            _ => ' ',
+        };
+        write!(f, "{}{:<7}  {:<23} ", prefix, self.device().sysname(), type_name(event_type))?;
+
+        if let EventType::DeviceAdd | EventType::DeviceRemove = self.event_type() {
+            return write!(f, "{}", self.device());
+        }
+
+        if let Some(time) = self.timestamp() {
+            let time = time.as_usec() as f64 - self.start().as_usec() as f64;
+            write!(f, "{:+6.3}s\t", time / 1_000_000.0)?;
+        }
+
+        match self.event_type() {
+            EventType::Keyboard(event) => {
+                write!(f, "{} ({}) {}", event.key, event.key.code(), state_name(event.state))
+            },
+            EventType::MouseMove(motion) => {
+                write!(f, "{:6.2}/{:6.2} ({:+6.2}/{:+6.2})",
+                       motion.dx, motion.dy, motion.dx_unaccelerated, motion.dy_unaccelerated)
+            },
+            EventType::MouseMoveAbsolute(_) => {
+                let (x, y) = self.transformed_position(SCREEN_SIZE, SCREEN_SIZE).unwrap_or((0.0, 0.0));
+                write!(f, "{:6.2}/{:6.2}", x, y)
+            },
+            EventType::MouseButton(event) | EventType::TabletButton(event) => {
+                write!(f, "{} ({}) {}, seat count: {}",
+                       event.button, event.button.code(), state_name(event.state), event.seat_button_count)
+            },
+            EventType::MouseAxis(axis) => {
+                let source = match axis.source {
+                    Source::Wheel => "wheel",
+                    Source::Finger => "finger",
+                    Source::Continuous => "continuous",
+                    Source::WheelTilt => "tilt",
+                };
+                write!(f, "vert {:.2}/{:.1}{} horiz {:.2}/{:.1}{} ({})",
+                       axis.vertical.unwrap_or(0.0), axis.vertical_discrete.unwrap_or(0.0),
+                       marker(axis.vertical.is_some()),
+                       axis.horizontal.unwrap_or(0.0), axis.horizontal_discrete.unwrap_or(0.0),
+                       marker(axis.horizontal.is_some()),
+                       source)
+            },
+            EventType::TouchDown(touch) | EventType::TouchMotion(touch) => {
+                let (x, y) = self.transformed_position(SCREEN_SIZE, SCREEN_SIZE).unwrap_or((0.0, 0.0));
+                write!(f, "{} ({}) {:5.2}/{:5.2} ({:5.2}/{:5.2}mm)", touch.slot, touch.seat_slot, x, y, touch.x, touch.y)
+            },
+            EventType::GestureSwipeBegin(fingers)
+            | EventType::GesturePinchBegin(fingers) => write!(f, "{}", fingers),
+            EventType::GestureSwipeEnd(fingers, cancelled)
+            | EventType::GesturePinchEnd(fingers, cancelled) => {
+                write!(f, "{}{}", fingers, if cancelled { " cancelled" } else { "" })
+            },
+            EventType::GestureSwipeUpdate(fingers, dx, dy, dx_unaccel, dy_unaccel) => {
+                write!(f, "{} {:5.2}/{:5.2} ({:5.2}/{:5.2} unaccelerated)", fingers, dx, dy, dx_unaccel, dy_unaccel)
+            },
+            EventType::GesturePinchUpdate(fingers, dx, dy, dx_unaccel, dy_unaccel, scale, angle) => {
+                write!(f, "{} {:5.2}/{:5.2} ({:5.2}/{:5.2} unaccelerated) {:5.2} @ {:5.2}",
+                       fingers, dx, dy, dx_unaccel, dy_unaccel, scale, angle)
+            },
+            EventType::TabletAxis => unsafe { tablet_axes(f, libinput_event_get_tablet_tool_event(self.as_raw())) },
+            EventType::TabletProximity => unsafe { tablet_proximity(f, libinput_event_get_tablet_tool_event(self.as_raw())) },
+            EventType::TabletTip => unsafe {
+                let tablet_event = libinput_event_get_tablet_tool_event(self.as_raw());
+                tablet_axes(f, tablet_event)?;
+                match libinput_event_tablet_tool_get_tip_state(tablet_event) {
+                    libinput_tablet_tool_tip_state::LIBINPUT_TABLET_TOOL_TIP_DOWN => write!(f, " down"),
+                    libinput_tablet_tool_tip_state::LIBINPUT_TABLET_TOOL_TIP_UP => write!(f, " up"),
+                }
+            },
+            EventType::TabletpadButton => unsafe {
+                let pad_event = libinput_event_get_tablet_pad_event(self.as_raw());
+                let state = match libinput_event_tablet_pad_get_button_state(pad_event) {
+                    libinput_button_state::LIBINPUT_BUTTON_STATE_PRESSED => State::Pressed,
+                    libinput_button_state::LIBINPUT_BUTTON_STATE_RELEASED => State::Released,
+                };
+                write!(f, "{:3} {}", libinput_event_tablet_pad_get_button_number(pad_event), state_name(state))
+            },
+            EventType::TabletpadRing => unsafe {
+                let pad_event = libinput_event_get_tablet_pad_event(self.as_raw());
+                let source = match libinput_event_tablet_pad_get_ring_source(pad_event) {
+                    libinput_tablet_pad_ring_axis_source::LIBINPUT_TABLET_PAD_RING_SOURCE_FINGER => "finger",
+                    libinput_tablet_pad_ring_axis_source::LIBINPUT_TABLET_PAD_RING_SOURCE_UNKNOWN => "unknown",
+                };
+                write!(f, "ring {} position {:.2} (source {})",
+                       libinput_event_tablet_pad_get_ring_number(pad_event),
+                       libinput_event_tablet_pad_get_ring_position(pad_event),
+                       source)
+            },
+            EventType::TabletpadStrip => unsafe {
+                let pad_event = libinput_event_get_tablet_pad_event(self.as_raw());
+                let source = match libinput_event_tablet_pad_get_strip_source(pad_event) {
+                    libinput_tablet_pad_strip_axis_source::LIBINPUT_TABLET_PAD_STRIP_SOURCE_FINGER => "finger",
+                    libinput_tablet_pad_strip_axis_source::LIBINPUT_TABLET_PAD_STRIP_SOURCE_UNKNOWN => "unknown",
+                };
+                write!(f, "strip {} position {:.2} (source {})",
+                       libinput_event_tablet_pad_get_strip_number(pad_event),
+                       libinput_event_tablet_pad_get_strip_position(pad_event),
+                       source)
+            },
+            EventType::SwitchToggle => unsafe {
+                let switch_event = libinput_event_get_switch_event(self.as_raw());
+                let state = match libinput_event_switch_get_switch_state(switch_event) {
+                    libinput_switch_state::LIBINPUT_SWITCH_STATE_ON => 1,
+                    libinput_switch_state::LIBINPUT_SWITCH_STATE_OFF => 0,
+                };
+                match libinput_event_switch_get_switch(switch_event) {
+                    libinput_switch::LIBINPUT_SWITCH_LID => write!(f, "switch lid state {}", state),
+                }
+            },
+            // Touch up, cancel and frame carry nothing but the time.
+            _ => Ok(()),
+        }
+    }
+}
+
+fn type_name(event_type: libinput_event_type) -> &'static str {
+    use libinput_event_type::*;
+
+    match event_type {
+        LIBINPUT_EVENT_NONE => "NONE",
+        LIBINPUT_EVENT_DEVICE_ADDED => "DEVICE_ADDED",
+        LIBINPUT_EVENT_DEVICE_REMOVED => "DEVICE_REMOVED",
+        LIBINPUT_EVENT_KEYBOARD_KEY => "KEYBOARD_KEY",
+        LIBINPUT_EVENT_POINTER_MOTION => "POINTER_MOTION",
+        LIBINPUT_EVENT_POINTER_MOTION_ABSOLUTE => "POINTER_MOTION_ABSOLUTE",
+        LIBINPUT_EVENT_POINTER_BUTTON => "POINTER_BUTTON",
+        LIBINPUT_EVENT_POINTER_AXIS => "POINTER_AXIS",
+        LIBINPUT_EVENT_TOUCH_DOWN => "TOUCH_DOWN",
+        LIBINPUT_EVENT_TOUCH_UP => "TOUCH_UP",
+        LIBINPUT_EVENT_TOUCH_MOTION => "TOUCH_MOTION",
+        LIBINPUT_EVENT_TOUCH_CANCEL => "TOUCH_CANCEL",
+        LIBINPUT_EVENT_TOUCH_FRAME => "TOUCH_FRAME",
+        LIBINPUT_EVENT_TABLET_TOOL_AXIS => "TABLET_TOOL_AXIS",
+        LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY => "TABLET_TOOL_PROXIMITY",
+        LIBINPUT_EVENT_TABLET_TOOL_TIP => "TABLET_TOOL_TIP",
+        LIBINPUT_EVENT_TABLET_TOOL_BUTTON => "TABLET_TOOL_BUTTON",
+        LIBINPUT_EVENT_TABLET_PAD_BUTTON => "TABLET_PAD_BUTTON",
+        LIBINPUT_EVENT_TABLET_PAD_RING => "TABLET_PAD_RING",
+        LIBINPUT_EVENT_TABLET_PAD_STRIP => "TABLET_PAD_STRIP",
+        LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN => "GESTURE_SWIPE_BEGIN",
+        LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE => "GESTURE_SWIPE_UPDATE",
+        LIBINPUT_EVENT_GESTURE_SWIPE_END => "GESTURE_SWIPE_END",
+        LIBINPUT_EVENT_GESTURE_PINCH_BEGIN => "GESTURE_PINCH_BEGIN",
+        LIBINPUT_EVENT_GESTURE_PINCH_UPDATE => "GESTURE_PINCH_UPDATE",
+        LIBINPUT_EVENT_GESTURE_PINCH_END => "GESTURE_PINCH_END",
+        LIBINPUT_EVENT_SWITCH_TOGGLE => "SWITCH_TOGGLE",
+    }
+}
+
+fn state_name(state: State) -> &'static str {
+    match state {
+        State::Pressed => "pressed",
+        State::Released => "released",
+    }
+}
+
+fn marker(changed: bool) -> &'static str {
+    if changed { "*" } else { "" }
+}
+
+fn changed(has_changed: c_int) -> &'static str {
+    marker(has_changed != 0)
+}
+
+// Position and whichever axes the tool has, changed ones marked.
+unsafe fn tablet_axes(f: &mut fmt::Formatter, event: *mut libinput_event_tablet_tool) -> fmt::Result {
+    let tool = libinput_event_tablet_tool_get_tool(event);
+
+    write!(f, "\t{:.2}{}/{:.2}{}",
+           libinput_event_tablet_tool_get_x(event), changed(libinput_event_tablet_tool_x_has_changed(event)),
+           libinput_event_tablet_tool_get_y(event), changed(libinput_event_tablet_tool_y_has_changed(event)))?;
+
+    if libinput_tablet_tool_has_tilt(tool) != 0 {
+        write!(f, "\ttilt: {:.2}{}/{:.2}{}",
+               libinput_event_tablet_tool_get_tilt_x(event), changed(libinput_event_tablet_tool_tilt_x_has_changed(event)),
+               libinput_event_tablet_tool_get_tilt_y(event), changed(libinput_event_tablet_tool_tilt_y_has_changed(event)))?;
+    }
+
+    if libinput_tablet_tool_has_distance(tool) != 0 || libinput_tablet_tool_has_pressure(tool) != 0 {
+        // A tool reports either distance or pressure, never both at once.
+        let distance = libinput_event_tablet_tool_get_distance(event);
+        if distance != 0.0 {
+            write!(f, "\tdistance: {:.2}{}", distance, changed(libinput_event_tablet_tool_distance_has_changed(event)))?;
+        }
+        else {
+            write!(f, "\tpressure: {:.2}{}",
+                   libinput_event_tablet_tool_get_pressure(event),
+                   changed(libinput_event_tablet_tool_pressure_has_changed(event)))?;
+        }
+    }
+
+    if libinput_tablet_tool_has_rotation(tool) != 0 {
+        write!(f, "\trotation: {:.2}{}",
+               libinput_event_tablet_tool_get_rotation(event),
+               changed(libinput_event_tablet_tool_rotation_has_changed(event)))?;
+    }
+
+    if libinput_tablet_tool_has_slider(tool) != 0 {
+        write!(f, "\tslider: {:.2}{}",
+               libinput_event_tablet_tool_get_slider_position(event),
+               changed(libinput_event_tablet_tool_slider_has_changed(event)))?;
+    }
+
+    if libinput_tablet_tool_has_wheel(tool) != 0 {
+        write!(f, "\twheel: {:.2}{} ({})",
+               libinput_event_tablet_tool_get_wheel_delta(event),
+               changed(libinput_event_tablet_tool_wheel_has_changed(event)),
+               libinput_event_tablet_tool_get_wheel_delta_discrete(event))?;
+    }
+
+    Ok(())
+}
+
+unsafe fn tablet_proximity(f: &mut fmt::Formatter, event: *mut libinput_event_tablet_tool) -> fmt::Result {
+    use libinput_tablet_tool_type::*;
+
+    let tool = libinput_event_tablet_tool_get_tool(event);
+    let tool_type = match libinput_tablet_tool_get_type(tool) {
+        LIBINPUT_TABLET_TOOL_TYPE_PEN => "pen",
+        LIBINPUT_TABLET_TOOL_TYPE_ERASER => "eraser",
+        LIBINPUT_TABLET_TOOL_TYPE_BRUSH => "brush",
+        LIBINPUT_TABLET_TOOL_TYPE_PENCIL => "pencil",
+        LIBINPUT_TABLET_TOOL_TYPE_AIRBRUSH => "airbrush",
+        LIBINPUT_TABLET_TOOL_TYPE_MOUSE => "mouse",
+        LIBINPUT_TABLET_TOOL_TYPE_LENS => "lens",
+    };
+
+    write!(f, "\t{} ({:#x}, id {:#x}) ",
+           tool_type, libinput_tablet_tool_get_serial(tool), libinput_tablet_tool_get_tool_id(tool))?;
+
+    match libinput_event_tablet_tool_get_proximity_state(event) {
+        libinput_tablet_tool_proximity_state::LIBINPUT_TABLET_TOOL_PROXIMITY_STATE_IN => {
+            write!(f, "proximity-in")?;
+            tablet_axes(f, event)
+        },
+        libinput_tablet_tool_proximity_state::LIBINPUT_TABLET_TOOL_PROXIMITY_STATE_OUT => write!(f, "proximity-out"),
+    }
+}
@@ -0,0 +1,248 @@
+use events::{Event, EventType};
+
+/// Swipe direction. Screen coordinates, so Up is towards negative y.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+/// A recognized touchpad gesture with its finger count.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Gesture {
+    Swipe(u8, Direction),
+    ZoomIn(u8),
+    ZoomOut(u8),
+    RotateClockwise(u8),
+    RotateCounterClockwise(u8),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GestureConfig {
+    /// Minimum swipe distance, in unaccelerated pointer units.
+    pub swipe_distance: f64,
+    /// Classify swipes into eight directions instead of four.
+    pub diagonals: bool,
+    /// Pinch scale at or above which the pinch is a zoom in.
+    pub zoom_in_scale: f64,
+    /// Pinch scale at or below which the pinch is a zoom out.
+    pub zoom_out_scale: f64,
+    /// Minimum accumulated rotation in degrees.
+    pub rotation: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> GestureConfig {
+        GestureConfig {
+            swipe_distance: 100.0,
+            diagonals: false,
+            zoom_in_scale: 1.25,
+            zoom_out_scale: 0.8,
+            rotation: 30.0,
+        }
+    }
+}
+
+/// Turns libinput's touchpad swipe and pinch sequences into gestures.
+/// Gestures are reported at the end of a sequence; cancelled sequences and
+/// those below the configured thresholds report nothing.
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    swipe: Option<(u8, f64, f64)>,
+    pinch: Option<(u8, f64, f64)>,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> GestureRecognizer {
+        GestureRecognizer {
+            config: config,
+            swipe: None,
+            pinch: None,
+        }
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    /// Feeds an event. Returns the gestures recognized at the end of a swipe
+    /// or pinch; a pinch can be both a zoom and a rotation.
+    pub fn feed(&mut self, event: &Event) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        match event.event_type() {
+            EventType::GestureSwipeBegin(fingers) => {
+                self.swipe = Some((fingers, 0.0, 0.0));
+            },
+            EventType::GestureSwipeUpdate(_, _, _, dx_unaccel, dy_unaccel) => {
+                if let Some((_, ref mut x, ref mut y)) = self.swipe {
+                    *x += dx_unaccel;
+                    *y += dy_unaccel;
+                }
+            },
+            EventType::GestureSwipeEnd(_, cancelled) => {
+                if let Some((fingers, x, y)) = self.swipe.take() {
+                    if !cancelled {
+                        if let Some(direction) = self.direction(x, y) {
+                            gestures.push(Gesture::Swipe(fingers, direction));
+                        }
+                    }
+                }
+            },
+            EventType::GesturePinchBegin(fingers) => {
+                self.pinch = Some((fingers, 1.0, 0.0));
+            },
+            EventType::GesturePinchUpdate(_, _, _, _, _, scale, angle_delta) => {
+                // libinput reports the scale relative to the begin, but the
+                // angle as a delta to the previous update.
+                if let Some((_, ref mut total_scale, ref mut angle)) = self.pinch {
+                    *total_scale = scale;
+                    *angle += angle_delta;
+                }
+            },
+            EventType::GesturePinchEnd(_, cancelled) => {
+                if let Some((fingers, scale, angle)) = self.pinch.take() {
+                    if !cancelled {
+                        if scale >= self.config.zoom_in_scale {
+                            gestures.push(Gesture::ZoomIn(fingers));
+                        }
+                        else if scale <= self.config.zoom_out_scale {
+                            gestures.push(Gesture::ZoomOut(fingers));
+                        }
+
+                        // Positive angles are clockwise.
+                        if angle >= self.config.rotation {
+                            gestures.push(Gesture::RotateClockwise(fingers));
+                        }
+                        else if angle <= -self.config.rotation {
+                            gestures.push(Gesture::RotateCounterClockwise(fingers));
+                        }
+                    }
+                }
+            },
+            _ => {},
+        }
+
+        gestures
+    }
+
+    fn direction(&self, x: f64, y: f64) -> Option<Direction> {
+        if x.hypot(y) < self.config.swipe_distance {
+            return None;
+        }
+
+        // Angle in degrees, 0 pointing right and increasing clockwise.
+        let angle = y.atan2(x).to_degrees();
+        let angle = if angle < 0.0 { angle + 360.0 } else { angle };
+
+        let direction = if self.config.diagonals {
+            match ((angle + 22.5) / 45.0) as u32 % 8 {
+                0 => Direction::Right,
+                1 => Direction::DownRight,
+                2 => Direction::Down,
+                3 => Direction::DownLeft,
+                4 => Direction::Left,
+                5 => Direction::UpLeft,
+                6 => Direction::Up,
+                _ => Direction::UpRight,
+            }
+        }
+        else {
+            match ((angle + 45.0) / 90.0) as u32 % 4 {
+                0 => Direction::Right,
+                1 => Direction::Down,
+                2 => Direction::Left,
+                _ => Direction::Up,
+            }
+        };
+
+        Some(direction)
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> GestureRecognizer {
+        GestureRecognizer::new(GestureConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, GestureConfig, GestureRecognizer};
+    use super::Direction::*;
+
+    fn recognizer(diagonals: bool) -> GestureRecognizer {
+        GestureRecognizer::new(GestureConfig { diagonals: diagonals, ..GestureConfig::default() })
+    }
+
+    // Direction of a swipe well past the threshold at the angle, in degrees
+    // clockwise from the right.
+    fn swipe(recognizer: &GestureRecognizer, degrees: f64) -> Option<Direction> {
+        let radians = degrees.to_radians();
+        recognizer.direction(200.0 * radians.cos(), 200.0 * radians.sin())
+    }
+
+    #[test]
+    fn four_directions() {
+        let recognizer = recognizer(false);
+        assert_eq!(recognizer.direction(150.0, 0.0), Some(Right));
+        assert_eq!(recognizer.direction(0.0, 150.0), Some(Down));
+        assert_eq!(recognizer.direction(-150.0, 0.0), Some(Left));
+        assert_eq!(recognizer.direction(0.0, -150.0), Some(Up));
+        assert_eq!(recognizer.direction(150.0, 150.0 - 1.0), Some(Right));
+        assert_eq!(recognizer.direction(-150.0, -150.0 + 1.0), Some(Left));
+    }
+
+    #[test]
+    fn four_way_boundaries_at_45_degrees() {
+        let recognizer = recognizer(false);
+        let boundaries = [(45.0, Right, Down), (135.0, Down, Left), (225.0, Left, Up), (315.0, Up, Right)];
+        for &(boundary, before, after) in &boundaries {
+            assert_eq!(swipe(&recognizer, boundary - 0.1), Some(before), "{}", boundary);
+            assert_eq!(swipe(&recognizer, boundary + 0.1), Some(after), "{}", boundary);
+        }
+    }
+
+    #[test]
+    fn eight_directions() {
+        let recognizer = recognizer(true);
+        let directions = [Right, DownRight, Down, DownLeft, Left, UpLeft, Up, UpRight];
+        for (i, &direction) in directions.iter().enumerate() {
+            assert_eq!(swipe(&recognizer, i as f64 * 45.0), Some(direction), "{:?}", direction);
+        }
+        assert_eq!(recognizer.direction(150.0, 150.0), Some(DownRight));
+        assert_eq!(recognizer.direction(-150.0, -150.0), Some(UpLeft));
+    }
+
+    #[test]
+    fn eight_way_boundaries_at_22_5_degrees() {
+        let recognizer = recognizer(true);
+        let directions = [Right, DownRight, Down, DownLeft, Left, UpLeft, Up, UpRight];
+        for i in 0..8 {
+            let boundary = 22.5 + i as f64 * 45.0;
+            assert_eq!(swipe(&recognizer, boundary - 0.1), Some(directions[i]), "{}", boundary);
+            assert_eq!(swipe(&recognizer, boundary + 0.1), Some(directions[(i + 1) % 8]), "{}", boundary);
+        }
+    }
+
+    #[test]
+    fn short_swipes_have_no_direction() {
+        for &diagonals in &[false, true] {
+            let recognizer = recognizer(diagonals);
+            assert_eq!(recognizer.direction(0.0, 0.0), None);
+            assert_eq!(recognizer.direction(99.0, 0.0), None);
+            assert_eq!(recognizer.direction(-60.0, 79.0), None);
+            assert_eq!(recognizer.direction(60.0, -80.0), Some(if diagonals { UpRight } else { Up }));
+        }
+    }
+}
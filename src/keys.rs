@@ -0,0 +1,546 @@
+event_codes! {
+    /// Keyboard key codes, the KEY_* range of linux/input-event-codes.h.
+    /// libinput reports keys as evdev codes; add 8 to get an X11/xkb keycode.
+    pub enum Key {
+        Reserved = 0x0 => "KEY_RESERVED",
+        Esc = 0x1 => "KEY_ESC",
+        Num1 = 0x2 => "KEY_1",
+        Num2 = 0x3 => "KEY_2",
+        Num3 = 0x4 => "KEY_3",
+        Num4 = 0x5 => "KEY_4",
+        Num5 = 0x6 => "KEY_5",
+        Num6 = 0x7 => "KEY_6",
+        Num7 = 0x8 => "KEY_7",
+        Num8 = 0x9 => "KEY_8",
+        Num9 = 0xa => "KEY_9",
+        Num0 = 0xb => "KEY_0",
+        Minus = 0xc => "KEY_MINUS",
+        Equal = 0xd => "KEY_EQUAL",
+        Backspace = 0xe => "KEY_BACKSPACE",
+        Tab = 0xf => "KEY_TAB",
+        Q = 0x10 => "KEY_Q",
+        W = 0x11 => "KEY_W",
+        E = 0x12 => "KEY_E",
+        R = 0x13 => "KEY_R",
+        T = 0x14 => "KEY_T",
+        Y = 0x15 => "KEY_Y",
+        U = 0x16 => "KEY_U",
+        I = 0x17 => "KEY_I",
+        O = 0x18 => "KEY_O",
+        P = 0x19 => "KEY_P",
+        LeftBrace = 0x1a => "KEY_LEFTBRACE",
+        RightBrace = 0x1b => "KEY_RIGHTBRACE",
+        Enter = 0x1c => "KEY_ENTER",
+        LeftCtrl = 0x1d => "KEY_LEFTCTRL",
+        A = 0x1e => "KEY_A",
+        S = 0x1f => "KEY_S",
+        D = 0x20 => "KEY_D",
+        F = 0x21 => "KEY_F",
+        G = 0x22 => "KEY_G",
+        H = 0x23 => "KEY_H",
+        J = 0x24 => "KEY_J",
+        K = 0x25 => "KEY_K",
+        L = 0x26 => "KEY_L",
+        Semicolon = 0x27 => "KEY_SEMICOLON",
+        Apostrophe = 0x28 => "KEY_APOSTROPHE",
+        Grave = 0x29 => "KEY_GRAVE",
+        LeftShift = 0x2a => "KEY_LEFTSHIFT",
+        Backslash = 0x2b => "KEY_BACKSLASH",
+        Z = 0x2c => "KEY_Z",
+        X = 0x2d => "KEY_X",
+        C = 0x2e => "KEY_C",
+        V = 0x2f => "KEY_V",
+        B = 0x30 => "KEY_B",
+        N = 0x31 => "KEY_N",
+        M = 0x32 => "KEY_M",
+        Comma = 0x33 => "KEY_COMMA",
+        Dot = 0x34 => "KEY_DOT",
+        Slash = 0x35 => "KEY_SLASH",
+        RightShift = 0x36 => "KEY_RIGHTSHIFT",
+        KpAsterisk = 0x37 => "KEY_KPASTERISK",
+        LeftAlt = 0x38 => "KEY_LEFTALT",
+        Space = 0x39 => "KEY_SPACE",
+        CapsLock = 0x3a => "KEY_CAPSLOCK",
+        F1 = 0x3b => "KEY_F1",
+        F2 = 0x3c => "KEY_F2",
+        F3 = 0x3d => "KEY_F3",
+        F4 = 0x3e => "KEY_F4",
+        F5 = 0x3f => "KEY_F5",
+        F6 = 0x40 => "KEY_F6",
+        F7 = 0x41 => "KEY_F7",
+        F8 = 0x42 => "KEY_F8",
+        F9 = 0x43 => "KEY_F9",
+        F10 = 0x44 => "KEY_F10",
+        NumLock = 0x45 => "KEY_NUMLOCK",
+        ScrollLock = 0x46 => "KEY_SCROLLLOCK",
+        Kp7 = 0x47 => "KEY_KP7",
+        Kp8 = 0x48 => "KEY_KP8",
+        Kp9 = 0x49 => "KEY_KP9",
+        KpMinus = 0x4a => "KEY_KPMINUS",
+        Kp4 = 0x4b => "KEY_KP4",
+        Kp5 = 0x4c => "KEY_KP5",
+        Kp6 = 0x4d => "KEY_KP6",
+        KpPlus = 0x4e => "KEY_KPPLUS",
+        Kp1 = 0x4f => "KEY_KP1",
+        Kp2 = 0x50 => "KEY_KP2",
+        Kp3 = 0x51 => "KEY_KP3",
+        Kp0 = 0x52 => "KEY_KP0",
+        KpDot = 0x53 => "KEY_KPDOT",
+        ZenkakuHankaku = 0x55 => "KEY_ZENKAKUHANKAKU",
+        Key102nd = 0x56 => "KEY_102ND",
+        F11 = 0x57 => "KEY_F11",
+        F12 = 0x58 => "KEY_F12",
+        Ro = 0x59 => "KEY_RO",
+        Katakana = 0x5a => "KEY_KATAKANA",
+        Hiragana = 0x5b => "KEY_HIRAGANA",
+        Henkan = 0x5c => "KEY_HENKAN",
+        KatakanaHiragana = 0x5d => "KEY_KATAKANAHIRAGANA",
+        Muhenkan = 0x5e => "KEY_MUHENKAN",
+        KpJpComma = 0x5f => "KEY_KPJPCOMMA",
+        KpEnter = 0x60 => "KEY_KPENTER",
+        RightCtrl = 0x61 => "KEY_RIGHTCTRL",
+        KpSlash = 0x62 => "KEY_KPSLASH",
+        SysRq = 0x63 => "KEY_SYSRQ",
+        RightAlt = 0x64 => "KEY_RIGHTALT",
+        LineFeed = 0x65 => "KEY_LINEFEED",
+        Home = 0x66 => "KEY_HOME",
+        Up = 0x67 => "KEY_UP",
+        PageUp = 0x68 => "KEY_PAGEUP",
+        Left = 0x69 => "KEY_LEFT",
+        Right = 0x6a => "KEY_RIGHT",
+        End = 0x6b => "KEY_END",
+        Down = 0x6c => "KEY_DOWN",
+        PageDown = 0x6d => "KEY_PAGEDOWN",
+        Insert = 0x6e => "KEY_INSERT",
+        Delete = 0x6f => "KEY_DELETE",
+        Macro = 0x70 => "KEY_MACRO",
+        Mute = 0x71 => "KEY_MUTE",
+        VolumeDown = 0x72 => "KEY_VOLUMEDOWN",
+        VolumeUp = 0x73 => "KEY_VOLUMEUP",
+        Power = 0x74 => "KEY_POWER",
+        KpEqual = 0x75 => "KEY_KPEQUAL",
+        KpPlusMinus = 0x76 => "KEY_KPPLUSMINUS",
+        Pause = 0x77 => "KEY_PAUSE",
+        Scale = 0x78 => "KEY_SCALE",
+        KpComma = 0x79 => "KEY_KPCOMMA",
+        Hangeul = 0x7a => "KEY_HANGEUL",
+        Hanja = 0x7b => "KEY_HANJA",
+        Yen = 0x7c => "KEY_YEN",
+        LeftMeta = 0x7d => "KEY_LEFTMETA",
+        RightMeta = 0x7e => "KEY_RIGHTMETA",
+        Compose = 0x7f => "KEY_COMPOSE",
+        Stop = 0x80 => "KEY_STOP",
+        Again = 0x81 => "KEY_AGAIN",
+        Props = 0x82 => "KEY_PROPS",
+        Undo = 0x83 => "KEY_UNDO",
+        Front = 0x84 => "KEY_FRONT",
+        Copy = 0x85 => "KEY_COPY",
+        Open = 0x86 => "KEY_OPEN",
+        Paste = 0x87 => "KEY_PASTE",
+        Find = 0x88 => "KEY_FIND",
+        Cut = 0x89 => "KEY_CUT",
+        Help = 0x8a => "KEY_HELP",
+        Menu = 0x8b => "KEY_MENU",
+        Calc = 0x8c => "KEY_CALC",
+        Setup = 0x8d => "KEY_SETUP",
+        Sleep = 0x8e => "KEY_SLEEP",
+        WakeUp = 0x8f => "KEY_WAKEUP",
+        File = 0x90 => "KEY_FILE",
+        SendFile = 0x91 => "KEY_SENDFILE",
+        DeleteFile = 0x92 => "KEY_DELETEFILE",
+        Xfer = 0x93 => "KEY_XFER",
+        Prog1 = 0x94 => "KEY_PROG1",
+        Prog2 = 0x95 => "KEY_PROG2",
+        Www = 0x96 => "KEY_WWW",
+        MsDos = 0x97 => "KEY_MSDOS",
+        Coffee = 0x98 => "KEY_COFFEE",
+        RotateDisplay = 0x99 => "KEY_ROTATE_DISPLAY",
+        CycleWindows = 0x9a => "KEY_CYCLEWINDOWS",
+        Mail = 0x9b => "KEY_MAIL",
+        Bookmarks = 0x9c => "KEY_BOOKMARKS",
+        Computer = 0x9d => "KEY_COMPUTER",
+        Back = 0x9e => "KEY_BACK",
+        Forward = 0x9f => "KEY_FORWARD",
+        CloseCd = 0xa0 => "KEY_CLOSECD",
+        EjectCd = 0xa1 => "KEY_EJECTCD",
+        EjectCloseCd = 0xa2 => "KEY_EJECTCLOSECD",
+        NextSong = 0xa3 => "KEY_NEXTSONG",
+        PlayPause = 0xa4 => "KEY_PLAYPAUSE",
+        PreviousSong = 0xa5 => "KEY_PREVIOUSSONG",
+        StopCd = 0xa6 => "KEY_STOPCD",
+        Record = 0xa7 => "KEY_RECORD",
+        Rewind = 0xa8 => "KEY_REWIND",
+        Phone = 0xa9 => "KEY_PHONE",
+        Iso = 0xaa => "KEY_ISO",
+        Config = 0xab => "KEY_CONFIG",
+        HomePage = 0xac => "KEY_HOMEPAGE",
+        Refresh = 0xad => "KEY_REFRESH",
+        Exit = 0xae => "KEY_EXIT",
+        Move = 0xaf => "KEY_MOVE",
+        Edit = 0xb0 => "KEY_EDIT",
+        ScrollUp = 0xb1 => "KEY_SCROLLUP",
+        ScrollDown = 0xb2 => "KEY_SCROLLDOWN",
+        KpLeftParen = 0xb3 => "KEY_KPLEFTPAREN",
+        KpRightParen = 0xb4 => "KEY_KPRIGHTPAREN",
+        New = 0xb5 => "KEY_NEW",
+        Redo = 0xb6 => "KEY_REDO",
+        F13 = 0xb7 => "KEY_F13",
+        F14 = 0xb8 => "KEY_F14",
+        F15 = 0xb9 => "KEY_F15",
+        F16 = 0xba => "KEY_F16",
+        F17 = 0xbb => "KEY_F17",
+        F18 = 0xbc => "KEY_F18",
+        F19 = 0xbd => "KEY_F19",
+        F20 = 0xbe => "KEY_F20",
+        F21 = 0xbf => "KEY_F21",
+        F22 = 0xc0 => "KEY_F22",
+        F23 = 0xc1 => "KEY_F23",
+        F24 = 0xc2 => "KEY_F24",
+        PlayCd = 0xc8 => "KEY_PLAYCD",
+        PauseCd = 0xc9 => "KEY_PAUSECD",
+        Prog3 = 0xca => "KEY_PROG3",
+        Prog4 = 0xcb => "KEY_PROG4",
+        AllApplications = 0xcc => "KEY_ALL_APPLICATIONS",
+        Suspend = 0xcd => "KEY_SUSPEND",
+        Close = 0xce => "KEY_CLOSE",
+        Play = 0xcf => "KEY_PLAY",
+        FastForward = 0xd0 => "KEY_FASTFORWARD",
+        BassBoost = 0xd1 => "KEY_BASSBOOST",
+        Print = 0xd2 => "KEY_PRINT",
+        Hp = 0xd3 => "KEY_HP",
+        Camera = 0xd4 => "KEY_CAMERA",
+        Sound = 0xd5 => "KEY_SOUND",
+        Question = 0xd6 => "KEY_QUESTION",
+        Email = 0xd7 => "KEY_EMAIL",
+        Chat = 0xd8 => "KEY_CHAT",
+        Search = 0xd9 => "KEY_SEARCH",
+        Connect = 0xda => "KEY_CONNECT",
+        Finance = 0xdb => "KEY_FINANCE",
+        Sport = 0xdc => "KEY_SPORT",
+        Shop = 0xdd => "KEY_SHOP",
+        AltErase = 0xde => "KEY_ALTERASE",
+        Cancel = 0xdf => "KEY_CANCEL",
+        BrightnessDown = 0xe0 => "KEY_BRIGHTNESSDOWN",
+        BrightnessUp = 0xe1 => "KEY_BRIGHTNESSUP",
+        Media = 0xe2 => "KEY_MEDIA",
+        SwitchVideoMode = 0xe3 => "KEY_SWITCHVIDEOMODE",
+        KbdIllumToggle = 0xe4 => "KEY_KBDILLUMTOGGLE",
+        KbdIllumDown = 0xe5 => "KEY_KBDILLUMDOWN",
+        KbdIllumUp = 0xe6 => "KEY_KBDILLUMUP",
+        Send = 0xe7 => "KEY_SEND",
+        Reply = 0xe8 => "KEY_REPLY",
+        ForwardMail = 0xe9 => "KEY_FORWARDMAIL",
+        Save = 0xea => "KEY_SAVE",
+        Documents = 0xeb => "KEY_DOCUMENTS",
+        Battery = 0xec => "KEY_BATTERY",
+        Bluetooth = 0xed => "KEY_BLUETOOTH",
+        Wlan = 0xee => "KEY_WLAN",
+        Uwb = 0xef => "KEY_UWB",
+        KeyUnknown = 0xf0 => "KEY_UNKNOWN",
+        VideoNext = 0xf1 => "KEY_VIDEO_NEXT",
+        VideoPrev = 0xf2 => "KEY_VIDEO_PREV",
+        BrightnessCycle = 0xf3 => "KEY_BRIGHTNESS_CYCLE",
+        BrightnessAuto = 0xf4 => "KEY_BRIGHTNESS_AUTO",
+        DisplayOff = 0xf5 => "KEY_DISPLAY_OFF",
+        Wwan = 0xf6 => "KEY_WWAN",
+        RfKill = 0xf7 => "KEY_RFKILL",
+        MicMute = 0xf8 => "KEY_MICMUTE",
+        Ok = 0x160 => "KEY_OK",
+        Select = 0x161 => "KEY_SELECT",
+        Goto = 0x162 => "KEY_GOTO",
+        Clear = 0x163 => "KEY_CLEAR",
+        Power2 = 0x164 => "KEY_POWER2",
+        Option = 0x165 => "KEY_OPTION",
+        Info = 0x166 => "KEY_INFO",
+        Time = 0x167 => "KEY_TIME",
+        Vendor = 0x168 => "KEY_VENDOR",
+        Archive = 0x169 => "KEY_ARCHIVE",
+        Program = 0x16a => "KEY_PROGRAM",
+        Channel = 0x16b => "KEY_CHANNEL",
+        Favorites = 0x16c => "KEY_FAVORITES",
+        Epg = 0x16d => "KEY_EPG",
+        Pvr = 0x16e => "KEY_PVR",
+        Mhp = 0x16f => "KEY_MHP",
+        Language = 0x170 => "KEY_LANGUAGE",
+        Title = 0x171 => "KEY_TITLE",
+        Subtitle = 0x172 => "KEY_SUBTITLE",
+        Angle = 0x173 => "KEY_ANGLE",
+        FullScreen = 0x174 => "KEY_FULL_SCREEN",
+        Mode = 0x175 => "KEY_MODE",
+        Keyboard = 0x176 => "KEY_KEYBOARD",
+        AspectRatio = 0x177 => "KEY_ASPECT_RATIO",
+        Pc = 0x178 => "KEY_PC",
+        Tv = 0x179 => "KEY_TV",
+        Tv2 = 0x17a => "KEY_TV2",
+        Vcr = 0x17b => "KEY_VCR",
+        Vcr2 = 0x17c => "KEY_VCR2",
+        Sat = 0x17d => "KEY_SAT",
+        Sat2 = 0x17e => "KEY_SAT2",
+        Cd = 0x17f => "KEY_CD",
+        Tape = 0x180 => "KEY_TAPE",
+        Radio = 0x181 => "KEY_RADIO",
+        Tuner = 0x182 => "KEY_TUNER",
+        Player = 0x183 => "KEY_PLAYER",
+        Text = 0x184 => "KEY_TEXT",
+        Dvd = 0x185 => "KEY_DVD",
+        Aux = 0x186 => "KEY_AUX",
+        Mp3 = 0x187 => "KEY_MP3",
+        Audio = 0x188 => "KEY_AUDIO",
+        Video = 0x189 => "KEY_VIDEO",
+        Directory = 0x18a => "KEY_DIRECTORY",
+        List = 0x18b => "KEY_LIST",
+        Memo = 0x18c => "KEY_MEMO",
+        Calendar = 0x18d => "KEY_CALENDAR",
+        Red = 0x18e => "KEY_RED",
+        Green = 0x18f => "KEY_GREEN",
+        Yellow = 0x190 => "KEY_YELLOW",
+        Blue = 0x191 => "KEY_BLUE",
+        ChannelUp = 0x192 => "KEY_CHANNELUP",
+        ChannelDown = 0x193 => "KEY_CHANNELDOWN",
+        First = 0x194 => "KEY_FIRST",
+        Last = 0x195 => "KEY_LAST",
+        Ab = 0x196 => "KEY_AB",
+        Next = 0x197 => "KEY_NEXT",
+        Restart = 0x198 => "KEY_RESTART",
+        Slow = 0x199 => "KEY_SLOW",
+        Shuffle = 0x19a => "KEY_SHUFFLE",
+        Break = 0x19b => "KEY_BREAK",
+        Previous = 0x19c => "KEY_PREVIOUS",
+        Digits = 0x19d => "KEY_DIGITS",
+        Teen = 0x19e => "KEY_TEEN",
+        Twen = 0x19f => "KEY_TWEN",
+        Videophone = 0x1a0 => "KEY_VIDEOPHONE",
+        Games = 0x1a1 => "KEY_GAMES",
+        ZoomIn = 0x1a2 => "KEY_ZOOMIN",
+        ZoomOut = 0x1a3 => "KEY_ZOOMOUT",
+        ZoomReset = 0x1a4 => "KEY_ZOOMRESET",
+        WordProcessor = 0x1a5 => "KEY_WORDPROCESSOR",
+        Editor = 0x1a6 => "KEY_EDITOR",
+        Spreadsheet = 0x1a7 => "KEY_SPREADSHEET",
+        GraphicsEditor = 0x1a8 => "KEY_GRAPHICSEDITOR",
+        Presentation = 0x1a9 => "KEY_PRESENTATION",
+        Database = 0x1aa => "KEY_DATABASE",
+        News = 0x1ab => "KEY_NEWS",
+        VoiceMail = 0x1ac => "KEY_VOICEMAIL",
+        AddressBook = 0x1ad => "KEY_ADDRESSBOOK",
+        Messenger = 0x1ae => "KEY_MESSENGER",
+        DisplayToggle = 0x1af => "KEY_DISPLAYTOGGLE",
+        SpellCheck = 0x1b0 => "KEY_SPELLCHECK",
+        Logoff = 0x1b1 => "KEY_LOGOFF",
+        Dollar = 0x1b2 => "KEY_DOLLAR",
+        Euro = 0x1b3 => "KEY_EURO",
+        FrameBack = 0x1b4 => "KEY_FRAMEBACK",
+        FrameForward = 0x1b5 => "KEY_FRAMEFORWARD",
+        ContextMenu = 0x1b6 => "KEY_CONTEXT_MENU",
+        MediaRepeat = 0x1b7 => "KEY_MEDIA_REPEAT",
+        Key10ChannelsUp = 0x1b8 => "KEY_10CHANNELSUP",
+        Key10ChannelsDown = 0x1b9 => "KEY_10CHANNELSDOWN",
+        Images = 0x1ba => "KEY_IMAGES",
+        NotificationCenter = 0x1bc => "KEY_NOTIFICATION_CENTER",
+        PickupPhone = 0x1bd => "KEY_PICKUP_PHONE",
+        HangupPhone = 0x1be => "KEY_HANGUP_PHONE",
+        LinkPhone = 0x1bf => "KEY_LINK_PHONE",
+        DelEol = 0x1c0 => "KEY_DEL_EOL",
+        DelEos = 0x1c1 => "KEY_DEL_EOS",
+        InsLine = 0x1c2 => "KEY_INS_LINE",
+        DelLine = 0x1c3 => "KEY_DEL_LINE",
+        Fn = 0x1d0 => "KEY_FN",
+        FnEsc = 0x1d1 => "KEY_FN_ESC",
+        FnF1 = 0x1d2 => "KEY_FN_F1",
+        FnF2 = 0x1d3 => "KEY_FN_F2",
+        FnF3 = 0x1d4 => "KEY_FN_F3",
+        FnF4 = 0x1d5 => "KEY_FN_F4",
+        FnF5 = 0x1d6 => "KEY_FN_F5",
+        FnF6 = 0x1d7 => "KEY_FN_F6",
+        FnF7 = 0x1d8 => "KEY_FN_F7",
+        FnF8 = 0x1d9 => "KEY_FN_F8",
+        FnF9 = 0x1da => "KEY_FN_F9",
+        FnF10 = 0x1db => "KEY_FN_F10",
+        FnF11 = 0x1dc => "KEY_FN_F11",
+        FnF12 = 0x1dd => "KEY_FN_F12",
+        Fn1 = 0x1de => "KEY_FN_1",
+        Fn2 = 0x1df => "KEY_FN_2",
+        FnD = 0x1e0 => "KEY_FN_D",
+        FnE = 0x1e1 => "KEY_FN_E",
+        FnF = 0x1e2 => "KEY_FN_F",
+        FnS = 0x1e3 => "KEY_FN_S",
+        FnB = 0x1e4 => "KEY_FN_B",
+        FnRightShift = 0x1e5 => "KEY_FN_RIGHT_SHIFT",
+        BrlDot1 = 0x1f1 => "KEY_BRL_DOT1",
+        BrlDot2 = 0x1f2 => "KEY_BRL_DOT2",
+        BrlDot3 = 0x1f3 => "KEY_BRL_DOT3",
+        BrlDot4 = 0x1f4 => "KEY_BRL_DOT4",
+        BrlDot5 = 0x1f5 => "KEY_BRL_DOT5",
+        BrlDot6 = 0x1f6 => "KEY_BRL_DOT6",
+        BrlDot7 = 0x1f7 => "KEY_BRL_DOT7",
+        BrlDot8 = 0x1f8 => "KEY_BRL_DOT8",
+        BrlDot9 = 0x1f9 => "KEY_BRL_DOT9",
+        BrlDot10 = 0x1fa => "KEY_BRL_DOT10",
+        Numeric0 = 0x200 => "KEY_NUMERIC_0",
+        Numeric1 = 0x201 => "KEY_NUMERIC_1",
+        Numeric2 = 0x202 => "KEY_NUMERIC_2",
+        Numeric3 = 0x203 => "KEY_NUMERIC_3",
+        Numeric4 = 0x204 => "KEY_NUMERIC_4",
+        Numeric5 = 0x205 => "KEY_NUMERIC_5",
+        Numeric6 = 0x206 => "KEY_NUMERIC_6",
+        Numeric7 = 0x207 => "KEY_NUMERIC_7",
+        Numeric8 = 0x208 => "KEY_NUMERIC_8",
+        Numeric9 = 0x209 => "KEY_NUMERIC_9",
+        NumericStar = 0x20a => "KEY_NUMERIC_STAR",
+        NumericPound = 0x20b => "KEY_NUMERIC_POUND",
+        NumericA = 0x20c => "KEY_NUMERIC_A",
+        NumericB = 0x20d => "KEY_NUMERIC_B",
+        NumericC = 0x20e => "KEY_NUMERIC_C",
+        NumericD = 0x20f => "KEY_NUMERIC_D",
+        CameraFocus = 0x210 => "KEY_CAMERA_FOCUS",
+        WpsButton = 0x211 => "KEY_WPS_BUTTON",
+        TouchpadToggle = 0x212 => "KEY_TOUCHPAD_TOGGLE",
+        TouchpadOn = 0x213 => "KEY_TOUCHPAD_ON",
+        TouchpadOff = 0x214 => "KEY_TOUCHPAD_OFF",
+        CameraZoomIn = 0x215 => "KEY_CAMERA_ZOOMIN",
+        CameraZoomOut = 0x216 => "KEY_CAMERA_ZOOMOUT",
+        CameraUp = 0x217 => "KEY_CAMERA_UP",
+        CameraDown = 0x218 => "KEY_CAMERA_DOWN",
+        CameraLeft = 0x219 => "KEY_CAMERA_LEFT",
+        CameraRight = 0x21a => "KEY_CAMERA_RIGHT",
+        AttendantOn = 0x21b => "KEY_ATTENDANT_ON",
+        AttendantOff = 0x21c => "KEY_ATTENDANT_OFF",
+        AttendantToggle = 0x21d => "KEY_ATTENDANT_TOGGLE",
+        LightsToggle = 0x21e => "KEY_LIGHTS_TOGGLE",
+        AlsToggle = 0x230 => "KEY_ALS_TOGGLE",
+        RotateLockToggle = 0x231 => "KEY_ROTATE_LOCK_TOGGLE",
+        RefreshRateToggle = 0x232 => "KEY_REFRESH_RATE_TOGGLE",
+        ButtonConfig = 0x240 => "KEY_BUTTONCONFIG",
+        TaskManager = 0x241 => "KEY_TASKMANAGER",
+        Journal = 0x242 => "KEY_JOURNAL",
+        ControlPanel = 0x243 => "KEY_CONTROLPANEL",
+        AppSelect = 0x244 => "KEY_APPSELECT",
+        ScreenSaver = 0x245 => "KEY_SCREENSAVER",
+        VoiceCommand = 0x246 => "KEY_VOICECOMMAND",
+        Assistant = 0x247 => "KEY_ASSISTANT",
+        KbdLayoutNext = 0x248 => "KEY_KBD_LAYOUT_NEXT",
+        EmojiPicker = 0x249 => "KEY_EMOJI_PICKER",
+        Dictate = 0x24a => "KEY_DICTATE",
+        BrightnessMin = 0x250 => "KEY_BRIGHTNESS_MIN",
+        KbdInputAssistPrev = 0x260 => "KEY_KBDINPUTASSIST_PREV",
+        KbdInputAssistNext = 0x261 => "KEY_KBDINPUTASSIST_NEXT",
+        KbdInputAssistPrevGroup = 0x262 => "KEY_KBDINPUTASSIST_PREVGROUP",
+        KbdInputAssistNextGroup = 0x263 => "KEY_KBDINPUTASSIST_NEXTGROUP",
+        KbdInputAssistAccept = 0x264 => "KEY_KBDINPUTASSIST_ACCEPT",
+        KbdInputAssistCancel = 0x265 => "KEY_KBDINPUTASSIST_CANCEL",
+        RightUp = 0x266 => "KEY_RIGHT_UP",
+        RightDown = 0x267 => "KEY_RIGHT_DOWN",
+        LeftUp = 0x268 => "KEY_LEFT_UP",
+        LeftDown = 0x269 => "KEY_LEFT_DOWN",
+        RootMenu = 0x26a => "KEY_ROOT_MENU",
+        MediaTopMenu = 0x26b => "KEY_MEDIA_TOP_MENU",
+        Numeric11 = 0x26c => "KEY_NUMERIC_11",
+        Numeric12 = 0x26d => "KEY_NUMERIC_12",
+        AudioDesc = 0x26e => "KEY_AUDIO_DESC",
+        Key3DMode = 0x26f => "KEY_3D_MODE",
+        NextFavorite = 0x270 => "KEY_NEXT_FAVORITE",
+        StopRecord = 0x271 => "KEY_STOP_RECORD",
+        PauseRecord = 0x272 => "KEY_PAUSE_RECORD",
+        Vod = 0x273 => "KEY_VOD",
+        Unmute = 0x274 => "KEY_UNMUTE",
+        FastReverse = 0x275 => "KEY_FASTREVERSE",
+        SlowReverse = 0x276 => "KEY_SLOWREVERSE",
+        Data = 0x277 => "KEY_DATA",
+        OnScreenKeyboard = 0x278 => "KEY_ONSCREEN_KEYBOARD",
+        PrivacyScreenToggle = 0x279 => "KEY_PRIVACY_SCREEN_TOGGLE",
+        SelectiveScreenshot = 0x27a => "KEY_SELECTIVE_SCREENSHOT",
+        NextElement = 0x27b => "KEY_NEXT_ELEMENT",
+        PreviousElement = 0x27c => "KEY_PREVIOUS_ELEMENT",
+        AutopilotEngageToggle = 0x27d => "KEY_AUTOPILOT_ENGAGE_TOGGLE",
+        MarkWaypoint = 0x27e => "KEY_MARK_WAYPOINT",
+        Sos = 0x27f => "KEY_SOS",
+        NavChart = 0x280 => "KEY_NAV_CHART",
+        FishingChart = 0x281 => "KEY_FISHING_CHART",
+        SingleRangeRadar = 0x282 => "KEY_SINGLE_RANGE_RADAR",
+        DualRangeRadar = 0x283 => "KEY_DUAL_RANGE_RADAR",
+        RadarOverlay = 0x284 => "KEY_RADAR_OVERLAY",
+        TraditionalSonar = 0x285 => "KEY_TRADITIONAL_SONAR",
+        ClearVuSonar = 0x286 => "KEY_CLEARVU_SONAR",
+        SideVuSonar = 0x287 => "KEY_SIDEVU_SONAR",
+        NavInfo = 0x288 => "KEY_NAV_INFO",
+        BrightnessMenu = 0x289 => "KEY_BRIGHTNESS_MENU",
+        Macro1 = 0x290 => "KEY_MACRO1",
+        Macro2 = 0x291 => "KEY_MACRO2",
+        Macro3 = 0x292 => "KEY_MACRO3",
+        Macro4 = 0x293 => "KEY_MACRO4",
+        Macro5 = 0x294 => "KEY_MACRO5",
+        Macro6 = 0x295 => "KEY_MACRO6",
+        Macro7 = 0x296 => "KEY_MACRO7",
+        Macro8 = 0x297 => "KEY_MACRO8",
+        Macro9 = 0x298 => "KEY_MACRO9",
+        Macro10 = 0x299 => "KEY_MACRO10",
+        Macro11 = 0x29a => "KEY_MACRO11",
+        Macro12 = 0x29b => "KEY_MACRO12",
+        Macro13 = 0x29c => "KEY_MACRO13",
+        Macro14 = 0x29d => "KEY_MACRO14",
+        Macro15 = 0x29e => "KEY_MACRO15",
+        Macro16 = 0x29f => "KEY_MACRO16",
+        Macro17 = 0x2a0 => "KEY_MACRO17",
+        Macro18 = 0x2a1 => "KEY_MACRO18",
+        Macro19 = 0x2a2 => "KEY_MACRO19",
+        Macro20 = 0x2a3 => "KEY_MACRO20",
+        Macro21 = 0x2a4 => "KEY_MACRO21",
+        Macro22 = 0x2a5 => "KEY_MACRO22",
+        Macro23 = 0x2a6 => "KEY_MACRO23",
+        Macro24 = 0x2a7 => "KEY_MACRO24",
+        Macro25 = 0x2a8 => "KEY_MACRO25",
+        Macro26 = 0x2a9 => "KEY_MACRO26",
+        Macro27 = 0x2aa => "KEY_MACRO27",
+        Macro28 = 0x2ab => "KEY_MACRO28",
+        Macro29 = 0x2ac => "KEY_MACRO29",
+        Macro30 = 0x2ad => "KEY_MACRO30",
+        MacroRecordStart = 0x2b0 => "KEY_MACRO_RECORD_START",
+        MacroRecordStop = 0x2b1 => "KEY_MACRO_RECORD_STOP",
+        MacroPresetCycle = 0x2b2 => "KEY_MACRO_PRESET_CYCLE",
+        MacroPreset1 = 0x2b3 => "KEY_MACRO_PRESET1",
+        MacroPreset2 = 0x2b4 => "KEY_MACRO_PRESET2",
+        MacroPreset3 = 0x2b5 => "KEY_MACRO_PRESET3",
+        KbdLcdMenu1 = 0x2b8 => "KEY_KBD_LCD_MENU1",
+        KbdLcdMenu2 = 0x2b9 => "KEY_KBD_LCD_MENU2",
+        KbdLcdMenu3 = 0x2ba => "KEY_KBD_LCD_MENU3",
+        KbdLcdMenu4 = 0x2bb => "KEY_KBD_LCD_MENU4",
+        KbdLcdMenu5 = 0x2bc => "KEY_KBD_LCD_MENU5",
+    }
+}
+
+impl Key {
+    /// Modifier and lock keys. These don't produce key repeats.
+    pub fn is_modifier(&self) -> bool {
+        match *self {
+            Key::LeftCtrl | Key::RightCtrl
+            | Key::LeftShift | Key::RightShift
+            | Key::LeftAlt | Key::RightAlt
+            | Key::LeftMeta | Key::RightMeta
+            | Key::CapsLock | Key::NumLock | Key::ScrollLock => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Key;
+
+    #[test]
+    fn display_round_trips() {
+        for code in 0..0x300 {
+            let key = Key::from_code(code);
+            assert_eq!(key.to_string().parse(), Ok(key), "{}", key);
+        }
+    }
+
+    #[test]
+    fn parse_names() {
+        for name in &["KEY_LEFTCTRL", "key_leftctrl", "LEFTCTRL", "LeftCtrl", " leftctrl "] {
+            assert_eq!(name.parse(), Ok(Key::LeftCtrl), "{}", name);
+        }
+        assert_eq!("0x1d".parse(), Ok(Key::LeftCtrl));
+        assert!("NoSuchKey".parse::<Key>().is_err());
+        assert!("0xzz".parse::<Key>().is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use ::std::collections::HashMap;
+use events::{Capabilities, Device, Event, EventType, Leds, State};
+use keys::Key;
+use state::seat_entry;
+
+// The keyboards of a seat and the LEDs they all show. Shared by LedSync and
+// xkb::KeyboardState.
+#[derive(Default)]
+pub(crate) struct Keyboards {
+    leds: Leds,
+    devices: Vec<Device>,
+}
+
+impl Keyboards {
+    pub(crate) fn leds(&self) -> Leds {
+        self.leds
+    }
+
+    pub(crate) fn set_leds(&mut self, leds: Leds) {
+        self.leds = leds;
+        for keyboard in &self.devices {
+            keyboard.set_leds(leds);
+        }
+    }
+
+    // Picks up added keyboards and sets their LEDs, and forgets removed ones.
+    // Keyboards added before we were fed anything are picked up on their
+    // first key event.
+    pub(crate) fn feed(&mut self, event: &Event) {
+        let device = event.device();
+        match event.event_type() {
+            EventType::DeviceAdd if device.has_capability(Capabilities::KEYBOARD) => self.add(device),
+            EventType::Keyboard(_) => self.add(device),
+            EventType::DeviceRemove => self.devices.retain(|keyboard| keyboard != device),
+            _ => {},
+        }
+    }
+
+    fn add(&mut self, device: &Device) {
+        if !self.devices.contains(device) {
+            self.devices.push(device.clone());
+            device.set_leds(self.leds);
+        }
+    }
+}
+
+/// Keeps the lock LEDs of all keyboards on a seat in sync, for setups without
+/// a keymap. xkb::KeyboardState does the same from the xkb state.
+///
+/// Feed it every event. A lock key toggles its LED on the first press on the
+/// seat. Keyboards are picked up when added and set to the current state of
+/// their seat, so hot-plugged keyboards match the others.
+#[derive(Default)]
+pub struct LedSync {
+    seats: HashMap<String, Keyboards>,
+}
+
+impl LedSync {
+    pub fn new() -> LedSync {
+        LedSync::default()
+    }
+
+    /// LEDs currently on for the seat.
+    pub fn leds(&self, seat: &str) -> Leds {
+        self.seats.get(seat).map_or(Leds::empty(), |seat| seat.leds())
+    }
+
+    /// Overrides the state of the seat, e.g. to restore it on startup.
+    pub fn set_leds(&mut self, seat: &str, leds: Leds) {
+        seat_entry(&mut self.seats, seat).set_leds(leds);
+    }
+
+    /// Returns whether the event changed the LEDs of its seat.
+    pub fn feed(&mut self, event: &Event) -> bool {
+        let seat = seat_entry(&mut self.seats, event.device().logical_seat());
+        seat.feed(event);
+
+        match event.event_type() {
+            EventType::Keyboard(key_event) => {
+                if key_event.state != State::Pressed || key_event.seat_key_count != 1 {
+                    return false;
+                }
+
+                let led = match key_event.key {
+                    Key::NumLock => Leds::NUM_LOCK,
+                    Key::CapsLock => Leds::CAPS_LOCK,
+                    Key::ScrollLock => Leds::SCROLL_LOCK,
+                    _ => return false,
+                };
+
+                let mut leds = seat.leds();
+                leds.toggle(led);
+                seat.set_leds(leds);
+                true
+            },
+            _ => false,
+        }
+    }
+}
@@ -5,13 +5,43 @@
 /// User needs to be part of input group to run this program as non-root.
 extern crate libinput_sys;
 extern crate libc;
-
+#[macro_use]
+extern crate bitflags;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "xkb")]
+extern crate xkbcommon;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[macro_use]
+mod macros;
+
+pub mod bindings;
+pub mod buttons;
+pub mod config;
+pub mod cursor;
 pub mod events;
-use events::{Device, Event};
+mod format;
+pub mod gestures;
+pub mod keys;
+pub mod leds;
+pub mod mapping;
+pub mod repeat;
+pub mod state;
+pub mod time;
+pub mod touch;
+pub mod udev_device;
+#[cfg(feature = "xkb")]
+pub mod xkb;
+use events::{Device, Event, EventClass};
+use time::Timestamp;
 
 use ::libinput_sys::*;
 use ::std::os::raw::{c_char, c_int, c_void};
 use ::std::ffi::CString;
+use ::std::os::unix::io::{AsRawFd, RawFd};
 
 const screen_width: u32 = 100;
 const screen_height: u32 = 100;
@@ -47,19 +77,6 @@ fn default_options() -> tools_context {
         grab: 0,
 
         verbose: 0,
-        tapping: -1,
-        tap_map: libinput_config_tap_button_map::LIBINPUT_CONFIG_TAP_MAP_LRM,
-        drag: -1,
-        drag_lock: -1,
-        natural_scroll: -1,
-        left_handed: -1,
-        middlebutton: -1,
-        dwt: -1,
-        click_method: libinput_config_click_method::LIBINPUT_CONFIG_CLICK_METHOD_NONE,
-        scroll_method: libinput_config_scroll_method::LIBINPUT_CONFIG_SCROLL_NO_SCROLL,
-        scroll_button: -1,
-        speed: 0_f64,
-        profile: libinput_config_accel_profile::LIBINPUT_CONFIG_ACCEL_PROFILE_NONE,
     };
 
     tools_context {
@@ -70,6 +87,10 @@ fn default_options() -> tools_context {
 
 pub struct LibInput {
     lib_handle: *mut libinput,
+    // Time of the first timestamped event, printed event times are relative
+    // to it.
+    start: Option<Timestamp>,
+    options: config::Options,
 }
 
 impl LibInput {
@@ -102,7 +123,17 @@ impl LibInput {
 
         unsafe { udev_unref(udev) };
 
-        Ok(LibInput { lib_handle: lib_handle })
+        Ok(LibInput { lib_handle: lib_handle, start: None, options: config::Options::default() })
+    }
+
+    pub fn options(&self) -> &config::Options {
+        &self.options
+    }
+
+    /// Configuration applied to devices as they are added. Options left at
+    /// None keep the device default.
+    pub fn options_mut(&mut self) -> &mut config::Options {
+        &mut self.options
     }
 
     pub fn events(&mut self) -> EventIterator {
@@ -110,6 +141,14 @@ impl LibInput {
     }
 }
 
+/// The libinput fd, for use in external event loops.
+/// When it becomes readable, drain events with events().next_until(Some(Timestamp::now())).
+impl AsRawFd for LibInput {
+    fn as_raw_fd(&self) -> RawFd {
+        unsafe { libinput_get_fd(self.lib_handle) }
+    }
+}
+
 impl Drop for LibInput {
     fn drop(&mut self) {
         // Return value ignored here.
@@ -120,9 +159,14 @@ impl Drop for LibInput {
 
 /// An iterator over libinput events.
 /// next() blocks until next input.
+///
+/// Filters set with only() and filter_devices() run before an event is
+/// decoded, filtered events are dropped right away.
 pub struct EventIterator<'a> {
     handle: &'a mut LibInput,
     pollfd: ::libc::pollfd,
+    classes: EventClass,
+    device_filter: Option<Box<dyn FnMut(&Device) -> bool + 'a>>,
 }
 
 impl<'a> EventIterator<'a> {
@@ -136,28 +180,99 @@ impl<'a> EventIterator<'a> {
         EventIterator {
             handle: input,
             pollfd: pollfd,
+            classes: EventClass::all(),
+            device_filter: None,
+        }
+    }
+
+    /// Only yields events of the given classes.
+    pub fn only(mut self, classes: EventClass) -> EventIterator<'a> {
+        self.classes = classes;
+        self
+    }
+
+    /// Only yields events of devices matching the predicate.
+    pub fn filter_devices<F>(mut self, filter: F) -> EventIterator<'a>
+        where F: FnMut(&Device) -> bool + 'a
+    {
+        self.device_filter = Some(Box::new(filter));
+        self
+    }
+
+    // Whether a raw event passes the filters.
+    fn wanted(&mut self, event: *mut libinput_event) -> bool {
+        let class = EventClass::from_event_type(unsafe { libinput_event_get_type(event) });
+        if !self.classes.intersects(class) {
+            return false;
+        }
+
+        match self.device_filter {
+            Some(ref mut filter) => filter(&Device::from(event)),
+            None => true,
         }
     }
 
+    /// Like next(), but gives up waiting at `deadline`, e.g. a KeyRepeat
+    /// deadline. Returns None once the deadline has passed and no event is
+    /// pending; a deadline in the past makes this a non-blocking read.
+    pub fn next_until(&mut self, deadline: Option<Timestamp>) -> Option<Event> {
+        loop {
+            unsafe { libinput_dispatch((*self).handle.lib_handle) };
+            let event = unsafe { libinput_get_event((*self).handle.lib_handle) };
+
+            // No events left, poll file descriptor for more events.
+            if event.is_null() {
+                let timeout = match deadline {
+                    Some(deadline) => {
+                        let now = Timestamp::now();
+                        if deadline <= now {
+                            return None;
+                        }
+
+                        // Round up so we don't wake just before the deadline.
+                        // Deadlines further out than poll() can wait wake us
+                        // early and we poll again.
+                        let msec = (deadline.as_usec() - now.as_usec()).saturating_add(999) / 1000;
+                        msec.min(c_int::MAX as u64) as c_int
+                    },
+                    None => -1,
+                };
+
+                let ret = unsafe { ::libc::poll((&mut ((*self).pollfd)) as *mut _, 1, timeout) };
+                if ret <= -1 {
+                    return None;
+                }
+                continue;
+            }
+
+            if unsafe { libinput_event_get_type(event) } == libinput_event_type::LIBINPUT_EVENT_DEVICE_ADDED {
+                config::apply_options(&Device::from(event), &self.handle.options);
+            }
+
+            if !self.wanted(event) {
+                unsafe { libinput_event_destroy(event) };
+                continue;
+            }
+
+            // Event::from frees event_lib (also takes ownership)
+            let mut event = Event::from(event);
+            // Device added/removed events carry no libinput time, don't
+            // measure from them.
+            if let Some(time) = event.timestamp() {
+                self.handle.start.get_or_insert(time);
+            }
+            if let Some(start) = self.handle.start {
+                event.set_start(start);
+            }
+            return Some(event);
+        }
+    }
 }
 
 impl<'a> Iterator for EventIterator<'a> {
     type Item = Event;
     fn next(&mut self) -> Option<Event> {
-        unsafe { libinput_dispatch((*self).handle.lib_handle) };
-        let event = unsafe { libinput_get_event((*self).handle.lib_handle) };
-
-        // No events left, poll file descriptor for more events.
-        if event.is_null() {
-            let ret = unsafe { ::libc::poll((&mut ((*self).pollfd)) as *mut _, 1, -1) };
-            if ret <= -1 {
-                return None;
-            }
-            return self.next();
-        }
-
-        // Event::from frees event_lib (also takes ownership)
-        Some(Event::from(event))
+        self.next_until(None)
     }
 }
 
@@ -176,19 +291,6 @@ pub struct tools_options {
     pub grab: c_int,
 
     pub verbose: c_int,
-    pub tapping: c_int,
-    pub drag: c_int,
-    pub drag_lock: c_int,
-    pub natural_scroll: c_int,
-    pub left_handed: c_int,
-    pub middlebutton: c_int,
-    pub click_method: libinput_config_click_method,
-    pub scroll_method: libinput_config_scroll_method,
-    pub tap_map: libinput_config_tap_button_map,
-    pub scroll_button: c_int,
-    pub speed: f64,
-    pub dwt: c_int,
-    pub profile: libinput_config_accel_profile,
 }
 
 #[repr(C)]
@@ -0,0 +1,123 @@
+/// Generates an enum for a range of evdev codes from linux/input-event-codes.h.
+/// Codes without a named variant are kept in `Unknown` so that conversions
+/// from and to the raw code are lossless.
+macro_rules! event_codes {
+    (
+        $(#[$attr:meta])*
+        pub enum $name:ident {
+            $($variant:ident = $code:tt => $cname:tt,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+        pub enum $name {
+            $($variant,)*
+            Unknown(u32),
+        }
+
+        impl $name {
+            const NAMES: &'static [($name, &'static str, &'static str)] = &[
+                $(($name::$variant, $cname, stringify!($variant)),)*
+            ];
+
+            pub fn from_code(code: u32) -> $name {
+                match code {
+                    $($code => $name::$variant,)*
+                    _ => $name::Unknown(code),
+                }
+            }
+
+            pub fn code(&self) -> u32 {
+                match *self {
+                    $($name::$variant => $code,)*
+                    $name::Unknown(code) => code,
+                }
+            }
+
+            /// Name as defined in linux/input-event-codes.h. None for unknown codes.
+            pub fn name(&self) -> Option<&'static str> {
+                match *self {
+                    $($name::$variant => Some($cname),)*
+                    $name::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl From<u32> for $name {
+            fn from(code: u32) -> $name {
+                $name::from_code(code)
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(code: $name) -> u32 {
+                code.code()
+            }
+        }
+
+        /// Prints the kernel name, or the code in hex if it has none.
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self.name() {
+                    Some(name) => f.write_str(name),
+                    None => write!(f, "{:#x}", self.code()),
+                }
+            }
+        }
+
+        /// Serializes as the Display string.
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<$name, D::Error> {
+                use ::serde::de::Error;
+
+                let name: String = ::serde::Deserialize::deserialize(deserializer)?;
+                name.parse().map_err(D::Error::custom)
+            }
+        }
+
+        /// Parses the kernel name with or without its prefix, or the variant
+        /// name, ignoring case. Hex codes as printed by Display are accepted too.
+        impl ::std::str::FromStr for $name {
+            type Err = &'static str;
+            fn from_str(s: &str) -> Result<$name, &'static str> {
+                let s = s.trim();
+                for &(code, cname, variant) in $name::NAMES {
+                    if ::macros::code_name_matches(s, cname, variant) {
+                        return Ok(code);
+                    }
+                }
+
+                ::macros::parse_hex_code(s)
+                    .map($name::from_code)
+                    .ok_or(concat!("Unknown ", stringify!($name), " name"))
+            }
+        }
+    }
+}
+
+pub fn code_name_matches(s: &str, cname: &str, variant: &str) -> bool {
+    // "KEY_LEFTCTRL" also matches "leftctrl", "BTN_LEFT" matches "left".
+    let bare = match cname.find('_') {
+        Some(i) => &cname[i + 1..],
+        None => cname,
+    };
+
+    s.eq_ignore_ascii_case(cname) || s.eq_ignore_ascii_case(bare) || s.eq_ignore_ascii_case(variant)
+}
+
+pub fn parse_hex_code(s: &str) -> Option<u32> {
+    if s.starts_with("0x") || s.starts_with("0X") {
+        u32::from_str_radix(&s[2..], 16).ok()
+    }
+    else {
+        None
+    }
+}
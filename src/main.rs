@@ -1,21 +1,16 @@
 extern crate libinput;
 
-use libinput::events::{Event, EventType};
+use libinput::events::EventType;
+use libinput::keys::Key;
 
 fn main() {
     let mut input = libinput::LibInput::new_from_udev().unwrap();
     for e in input.events() {
-        let dev = e.device();
-        print!("{} {} {} ", dev.name(), dev.physical_seat(), dev.logical_seat());
-        match e.event_type() {
-            EventType::KeyboardInput(state, key) => {
-                if key == 1 { // Escape key
-                    break;
-                }
-                print!("Keypress {} ", key)
-            },
-            _ => {},
-        };
-        println!("{}", e.time());
+        if let EventType::Keyboard(event) = e.event_type() {
+            if event.key == Key::Esc {
+                break;
+            }
+        }
+        println!("{}", e);
     }
 }
@@ -0,0 +1,139 @@
+use cursor::{self, Output};
+use events::{Device, Event};
+
+// Absolute events are read at this resolution and then normalized to 0..1.
+const ABSOLUTE_SCALE: u32 = 1 << 20;
+
+/// Orientation of an absolute device relative to its output.
+/// Rotations are clockwise, flipped variants mirror horizontally first.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Transform {
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl Transform {
+    /// Applies the transform to normalized (0..1) device coordinates.
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        let (x, y) = match *self {
+            Transform::Flipped
+            | Transform::Flipped90
+            | Transform::Flipped180
+            | Transform::Flipped270 => (1.0 - x, y),
+            _ => (x, y),
+        };
+
+        match *self {
+            Transform::Normal | Transform::Flipped => (x, y),
+            Transform::Rotate90 | Transform::Flipped90 => (1.0 - y, x),
+            Transform::Rotate180 | Transform::Flipped180 => (1.0 - x, 1.0 - y),
+            Transform::Rotate270 | Transform::Flipped270 => (y, 1.0 - x),
+        }
+    }
+}
+
+/// Assigns absolute devices (touchscreens, tablets) to outputs.
+///
+/// Explicit rules match on the device name and take precedence. Otherwise the
+/// device's own output name from udev is used, unless automatic mapping is
+/// turned off. Unmapped devices span the whole layout.
+pub struct OutputMap {
+    rules: Vec<(String, String)>,
+    automatic: bool,
+}
+
+impl OutputMap {
+    pub fn new() -> OutputMap {
+        OutputMap {
+            rules: Vec::new(),
+            automatic: true,
+        }
+    }
+
+    /// Maps the device with the given name to the named output.
+    pub fn add_rule(&mut self, device_name: &str, output_name: &str) {
+        self.rules.push((device_name.to_owned(), output_name.to_owned()));
+    }
+
+    /// Whether devices without a rule follow their udev output name.
+    pub fn set_automatic(&mut self, automatic: bool) {
+        self.automatic = automatic;
+    }
+
+    /// Output the device is mapped to. None if it spans the whole layout.
+    pub fn output_for<'a>(&self, device: &Device, outputs: &'a [Output]) -> Option<&'a Output> {
+        let find = |name: &str| outputs.iter().find(|output| output.name == name);
+
+        let rule = self.rules.iter().find(|&&(ref device_name, _)| device_name == device.name());
+        if let Some(&(_, ref output_name)) = rule {
+            return find(output_name);
+        }
+
+        if self.automatic {
+            if let Some(output_name) = device.output_name() {
+                return find(output_name);
+            }
+        }
+
+        None
+    }
+
+    /// Position of an absolute event in layout coordinates, with the output's
+    /// transform applied. None for events without an absolute position or an
+    /// empty layout.
+    pub fn map(&self, event: &Event, outputs: &[Output]) -> Option<(f64, f64)> {
+        let (x, y) = match event.transformed_position(ABSOLUTE_SCALE, ABSOLUTE_SCALE) {
+            Some((x, y)) => (x / ABSOLUTE_SCALE as f64, y / ABSOLUTE_SCALE as f64),
+            None => return None,
+        };
+
+        let (rect, (x, y)) = match self.output_for(event.device(), outputs) {
+            Some(output) => (output.rect, output.transform.apply(x, y)),
+            None => match cursor::bounds(outputs) {
+                Some(bounds) => (bounds, (x, y)),
+                None => return None,
+            },
+        };
+
+        Some((rect.x + x * rect.width, rect.y + y * rect.height))
+    }
+}
+
+impl Default for OutputMap {
+    fn default() -> OutputMap {
+        OutputMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform;
+
+    #[test]
+    fn transforms_move_top_left_corner() {
+        assert_eq!(Transform::Normal.apply(0.0, 0.0), (0.0, 0.0));
+        assert_eq!(Transform::Rotate90.apply(0.0, 0.0), (1.0, 0.0));
+        assert_eq!(Transform::Rotate180.apply(0.0, 0.0), (1.0, 1.0));
+        assert_eq!(Transform::Rotate270.apply(0.0, 0.0), (0.0, 1.0));
+        assert_eq!(Transform::Flipped.apply(0.0, 0.0), (1.0, 0.0));
+        assert_eq!(Transform::Flipped90.apply(0.0, 0.0), (1.0, 1.0));
+        assert_eq!(Transform::Flipped180.apply(0.0, 0.0), (0.0, 1.0));
+        assert_eq!(Transform::Flipped270.apply(0.0, 0.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn flipped_transforms_mirror_first() {
+        assert_eq!(Transform::Rotate90.apply(0.25, 0.5), (0.5, 0.25));
+        assert_eq!(Transform::Rotate270.apply(0.25, 0.5), (0.5, 0.75));
+        assert_eq!(Transform::Flipped.apply(0.25, 0.5), (0.75, 0.5));
+        assert_eq!(Transform::Flipped90.apply(0.25, 0.5), (0.5, 0.75));
+        assert_eq!(Transform::Flipped180.apply(0.25, 0.5), (0.25, 0.5));
+        assert_eq!(Transform::Flipped270.apply(0.25, 0.5), (0.5, 0.25));
+    }
+}
@@ -0,0 +1,162 @@
+use ::std::time::Duration;
+use events::{Device, Event, EventType, State};
+use keys::Key;
+use time::Timestamp;
+
+/// A synthetic key press generated by KeyRepeat.
+#[derive(Clone, Debug)]
+pub struct RepeatEvent {
+    pub device: Device,
+    pub key: Key,
+    /// When the repeat was due, not when it was polled.
+    pub time: Timestamp,
+}
+
+// Most repeats a single poll() returns. Repeats missed beyond that, e.g.
+// after the caller stalled, are dropped rather than delivered in a burst.
+const MAX_REPEATS_PER_POLL: usize = 32;
+
+struct Held {
+    device: Device,
+    key: Key,
+    next: Timestamp,
+}
+
+/// Software key repeat, since libinput doesn't do any.
+///
+/// Feed it every event, then call poll() once next_deadline() has passed.
+/// With the blocking iterator that looks like:
+///
+/// ```ignore
+/// let mut events = input.events();
+/// loop {
+///     if let Some(event) = events.next_until(repeat.next_deadline()) {
+///         repeat.feed(&event);
+///     }
+///     for key in repeat.poll(Timestamp::now()) { ... }
+/// }
+/// ```
+///
+/// Only the most recently pressed key repeats, modifiers never do.
+pub struct KeyRepeat {
+    delay: Duration,
+    interval: Option<Duration>,
+    held: Option<Held>,
+}
+
+impl KeyRepeat {
+    /// `rate` is in repeats per second, from 1 to 1000000.
+    pub fn new(delay: Duration, rate: u32) -> Result<KeyRepeat, &'static str> {
+        let mut repeat = KeyRepeat {
+            delay: delay,
+            interval: None,
+            held: None,
+        };
+        repeat.set_rate(rate)?;
+        Ok(repeat)
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// Sets the rate in repeats per second and enables repeat.
+    pub fn set_rate(&mut self, rate: u32) -> Result<(), &'static str> {
+        if rate == 0 || rate > 1_000_000 {
+            return Err("Repeat rate must be between 1 and 1000000 per second");
+        }
+
+        self.interval = Some(Duration::from_micros(1_000_000 / rate as u64));
+        Ok(())
+    }
+
+    /// Turns repeat off until the next set_rate().
+    pub fn disable(&mut self) {
+        self.interval = None;
+        self.held = None;
+    }
+
+    pub fn feed(&mut self, event: &Event) {
+        match event.event_type() {
+            EventType::Keyboard(key_event) => {
+                match key_event.state {
+                    State::Pressed => {
+                        if key_event.key.is_modifier() || self.interval.is_none() {
+                            return;
+                        }
+
+                        // A delay too long to represent never repeats.
+                        let next = match event.timestamp().and_then(|time| time.checked_add(self.delay)) {
+                            Some(next) => next,
+                            None => return,
+                        };
+
+                        self.held = Some(Held {
+                            device: event.device().clone(),
+                            key: key_event.key,
+                            next: next,
+                        });
+                    },
+                    State::Released => {
+                        let released = match self.held {
+                            Some(ref held) => held.key == key_event.key && held.device == *event.device(),
+                            None => false,
+                        };
+
+                        if released {
+                            self.held = None;
+                        }
+                    },
+                }
+            },
+            EventType::DeviceRemove => {
+                let removed = match self.held {
+                    Some(ref held) => held.device == *event.device(),
+                    None => false,
+                };
+
+                if removed {
+                    self.held = None;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Stops repeating the held key, e.g. when focus changes.
+    pub fn cancel(&mut self) {
+        self.held = None;
+    }
+
+    /// Time the next repeat is due. None while no key repeats.
+    pub fn next_deadline(&self) -> Option<Timestamp> {
+        self.held.as_ref().map(|held| held.next)
+    }
+
+    /// Returns the repeats due at `now`, oldest first. If more are due than
+    /// one poll returns, the rest are skipped and repeat resumes from `now`.
+    pub fn poll(&mut self, now: Timestamp) -> Vec<RepeatEvent> {
+        let mut repeats = Vec::new();
+
+        if let (Some(held), Some(interval)) = (self.held.as_mut(), self.interval) {
+            while held.next <= now && repeats.len() < MAX_REPEATS_PER_POLL {
+                repeats.push(RepeatEvent {
+                    device: held.device.clone(),
+                    key: held.key,
+                    time: held.next,
+                });
+                held.next = held.next + interval;
+            }
+
+            if held.next <= now {
+                held.next = now + interval;
+            }
+        }
+
+        repeats
+    }
+}
@@ -0,0 +1,168 @@
+use ::std::collections::{HashMap, HashSet};
+use ::std::hash::Hash;
+use buttons::Button;
+use events::{Device, Event, EventType, State};
+use keys::Key;
+
+/// Tracks which keys and buttons are held, per device and per seat.
+///
+/// Seat state follows libinput's seat key/button counts, so a key held on two
+/// keyboards stays down until both release it. Like libinput, pointer and
+/// tablet tool buttons are counted separately per seat.
+#[derive(Default)]
+pub struct InputState {
+    device_keys: HashMap<Device, HashSet<Key>>,
+    device_buttons: HashMap<Device, HashSet<Button>>,
+    device_tool_buttons: HashMap<Device, HashSet<Button>>,
+    seat_keys: HashMap<String, HashMap<Key, u32>>,
+    seat_buttons: HashMap<String, HashMap<Button, u32>>,
+    seat_tool_buttons: HashMap<String, HashMap<Button, u32>>,
+}
+
+impl InputState {
+    pub fn new() -> InputState {
+        InputState::default()
+    }
+
+    pub fn feed(&mut self, event: &Event) {
+        let device = event.device();
+
+        match event.event_type() {
+            EventType::Keyboard(key_event) => {
+                update_device(&mut self.device_keys, device, key_event.key, key_event.state);
+                update_seat(&mut self.seat_keys, device.logical_seat(), key_event.key, key_event.seat_key_count);
+            },
+            EventType::MouseButton(button_event) => {
+                update_device(&mut self.device_buttons, device, button_event.button, button_event.state);
+                update_seat(&mut self.seat_buttons, device.logical_seat(), button_event.button,
+                            button_event.seat_button_count);
+            },
+            EventType::TabletButton(button_event) => {
+                update_device(&mut self.device_tool_buttons, device, button_event.button, button_event.state);
+                update_seat(&mut self.seat_tool_buttons, device.logical_seat(), button_event.button,
+                            button_event.seat_button_count);
+            },
+            EventType::DeviceRemove => {
+                // libinput releases held keys before removing a device, this
+                // only matters if those events were never fed to us.
+                let seat = device.logical_seat();
+                if let Some(keys) = self.device_keys.remove(device) {
+                    release_seat(&mut self.seat_keys, seat, keys);
+                }
+                if let Some(buttons) = self.device_buttons.remove(device) {
+                    release_seat(&mut self.seat_buttons, seat, buttons);
+                }
+                if let Some(buttons) = self.device_tool_buttons.remove(device) {
+                    release_seat(&mut self.seat_tool_buttons, seat, buttons);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    pub fn is_key_down(&self, device: &Device, key: Key) -> bool {
+        self.device_keys.get(device).map_or(false, |keys| keys.contains(&key))
+    }
+
+    /// Whether the device holds the button, pointer or tablet tool.
+    pub fn is_button_down(&self, device: &Device, button: Button) -> bool {
+        self.device_buttons.get(device).map_or(false, |buttons| buttons.contains(&button))
+            || self.device_tool_buttons.get(device).map_or(false, |buttons| buttons.contains(&button))
+    }
+
+    /// Whether any keyboard on the seat holds the key.
+    pub fn is_seat_key_down(&self, seat: &str, key: Key) -> bool {
+        self.seat_keys.get(seat).map_or(false, |keys| keys.contains_key(&key))
+    }
+
+    /// Whether any pointer on the seat holds the button.
+    pub fn is_seat_button_down(&self, seat: &str, button: Button) -> bool {
+        self.seat_buttons.get(seat).map_or(false, |buttons| buttons.contains_key(&button))
+    }
+
+    /// Whether any tablet tool on the seat holds the button.
+    pub fn is_seat_tool_button_down(&self, seat: &str, button: Button) -> bool {
+        self.seat_tool_buttons.get(seat).map_or(false, |buttons| buttons.contains_key(&button))
+    }
+
+    /// Keys held on the device, sorted by code.
+    pub fn keys_down(&self, device: &Device) -> Vec<Key> {
+        self.device_keys.get(device).map_or(Vec::new(), |keys| sorted(keys.iter()))
+    }
+
+    /// Buttons held on the device, pointer and tablet tool, sorted by code.
+    pub fn buttons_down(&self, device: &Device) -> Vec<Button> {
+        let buttons = self.device_buttons.get(device).into_iter()
+            .chain(self.device_tool_buttons.get(device))
+            .flat_map(|buttons| buttons.iter());
+        let mut buttons = sorted(buttons);
+        buttons.dedup();
+        buttons
+    }
+
+    /// Keys held on any keyboard of the seat, sorted by code.
+    pub fn seat_keys_down(&self, seat: &str) -> Vec<Key> {
+        self.seat_keys.get(seat).map_or(Vec::new(), |keys| sorted(keys.keys()))
+    }
+
+    /// Buttons held on any pointer of the seat, sorted by code.
+    pub fn seat_buttons_down(&self, seat: &str) -> Vec<Button> {
+        self.seat_buttons.get(seat).map_or(Vec::new(), |buttons| sorted(buttons.keys()))
+    }
+
+    /// Buttons held on any tablet tool of the seat, sorted by code.
+    pub fn seat_tool_buttons_down(&self, seat: &str) -> Vec<Button> {
+        self.seat_tool_buttons.get(seat).map_or(Vec::new(), |buttons| sorted(buttons.keys()))
+    }
+}
+
+fn update_device<T: Eq + Hash>(held: &mut HashMap<Device, HashSet<T>>, device: &Device, code: T, state: State) {
+    match state {
+        State::Pressed => {
+            held.entry(device.clone()).or_default().insert(code);
+        },
+        State::Released => {
+            if let Some(codes) = held.get_mut(device) {
+                codes.remove(&code);
+            }
+        },
+    }
+}
+
+fn update_seat<T: Eq + Hash>(held: &mut HashMap<String, HashMap<T, u32>>, seat: &str, code: T, count: u32) {
+    update_seat_count(seat_entry(held, seat), code, count);
+}
+
+// Entry of a per-seat map. Only allocates the seat name for a new seat, this
+// runs for every event.
+pub(crate) fn seat_entry<'a, V: Default>(seats: &'a mut HashMap<String, V>, seat: &str) -> &'a mut V {
+    if !seats.contains_key(seat) {
+        seats.insert(seat.to_owned(), V::default());
+    }
+
+    seats.get_mut(seat).unwrap()
+}
+
+fn release_seat<T: Eq + Hash>(held: &mut HashMap<String, HashMap<T, u32>>, seat: &str, released: HashSet<T>) {
+    if let Some(codes) = held.get_mut(seat) {
+        for code in released {
+            let count = codes.get(&code).map_or(0, |count| count.saturating_sub(1));
+            update_seat_count(codes, code, count);
+        }
+    }
+}
+
+fn update_seat_count<T: Eq + Hash>(codes: &mut HashMap<T, u32>, code: T, count: u32) {
+    if count > 0 {
+        codes.insert(code, count);
+    }
+    else {
+        codes.remove(&code);
+    }
+}
+
+fn sorted<'a, T: 'a + Ord + Copy, I: Iterator<Item = &'a T>>(codes: I) -> Vec<T> {
+    let mut codes: Vec<T> = codes.cloned().collect();
+    codes.sort();
+    codes
+}
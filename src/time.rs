@@ -0,0 +1,85 @@
+use ::std::fmt;
+use ::std::ops::{Add, Sub};
+use ::std::time::{Duration, Instant};
+
+/// Point in time on the CLOCK_MONOTONIC clock, in microseconds.
+/// This is the clock libinput uses for all event timestamps.
+/// Serializes as the plain number of microseconds.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub fn from_usec(usec: u64) -> Timestamp {
+        Timestamp(usec)
+    }
+
+    pub fn as_usec(&self) -> u64 {
+        self.0
+    }
+
+    pub fn as_msec(&self) -> u64 {
+        self.0 / 1000
+    }
+
+    /// Timestamp `duration` later, None on overflow.
+    pub fn checked_add(&self, duration: Duration) -> Option<Timestamp> {
+        let usec = duration.as_secs().checked_mul(1_000_000)?.checked_add(duration.subsec_micros() as u64)?;
+        self.0.checked_add(usec).map(Timestamp)
+    }
+
+    /// Current time on CLOCK_MONOTONIC.
+    pub fn now() -> Timestamp {
+        let mut ts = ::libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        unsafe { ::libc::clock_gettime(::libc::CLOCK_MONOTONIC, &mut ts) };
+
+        Timestamp(ts.tv_sec as u64 * 1_000_000 + ts.tv_nsec as u64 / 1000)
+    }
+
+    /// Time elapsed from `earlier` to this timestamp, zero if `earlier` is later.
+    pub fn duration_since(&self, earlier: Timestamp) -> Duration {
+        Duration::from_micros(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Time elapsed since this timestamp, e.g. the delay between the kernel
+    /// seeing an input event and the caller handling it.
+    pub fn latency(&self) -> Duration {
+        Timestamp::now().duration_since(*self)
+    }
+
+    /// Converts to an Instant by offsetting Instant::now() with the latency.
+    /// Timestamps in the future map to now.
+    pub fn to_instant(&self) -> Instant {
+        let now = Instant::now();
+        now.checked_sub(self.latency()).unwrap_or(now)
+    }
+}
+
+/// Duration since the CLOCK_MONOTONIC epoch (usually boot).
+impl From<Timestamp> for Duration {
+    fn from(time: Timestamp) -> Duration {
+        Duration::from_micros(time.0)
+    }
+}
+
+/// Panics on overflow, see checked_add().
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+    fn add(self, rhs: Duration) -> Timestamp {
+        self.checked_add(rhs).expect("overflow when adding duration to timestamp")
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+    fn sub(self, rhs: Duration) -> Timestamp {
+        Timestamp(self.0.saturating_sub(rhs.as_secs() * 1_000_000 + rhs.subsec_micros() as u64))
+    }
+}
+
+/// Formats as seconds with microsecond precision.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{:06}", self.0 / 1_000_000, self.0 % 1_000_000)
+    }
+}
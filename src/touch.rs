@@ -0,0 +1,579 @@
+use ::std::collections::BTreeMap;
+use ::std::time::Duration;
+use events::{Device, Event, EventType};
+use time::Timestamp;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// A recognized touchscreen gesture. Positions and distances are in mm on
+/// the device.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TouchGesture {
+    /// Short touch without movement.
+    Tap(f64, f64),
+    /// Tap close to and shortly after the previous one. Reported right after
+    /// the Tap of the second touch.
+    DoubleTap(f64, f64),
+    /// Touch held in place for the long press time.
+    LongPress(f64, f64),
+    /// Finger count and movement of the touch centroid since the last Pan.
+    Pan(u8, f64, f64),
+    PanEnd(u8),
+    /// Distance between the first two fingers relative to the start.
+    Pinch(f64),
+    /// Rotation of the first two fingers since the start, in degrees clockwise.
+    Rotate(f64),
+    PinchEnd,
+    /// Single finger swipe from the edge of the device inwards.
+    EdgeSwipe(Edge),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TouchConfig {
+    /// Movement in mm below which a touch still counts as stationary.
+    pub tap_distance: f64,
+    pub tap_time: Duration,
+    pub double_tap_time: Duration,
+    pub double_tap_distance: f64,
+    pub long_press_time: Duration,
+    /// Relative change in finger distance that starts a pinch.
+    pub pinch_threshold: f64,
+    /// Rotation in degrees that starts a pinch.
+    pub rotate_threshold: f64,
+    /// Width in mm of the border where edge swipes start.
+    pub edge_margin: f64,
+    /// Distance in mm an edge swipe has to travel inwards.
+    pub edge_swipe_distance: f64,
+}
+
+impl Default for TouchConfig {
+    fn default() -> TouchConfig {
+        TouchConfig {
+            tap_distance: 3.0,
+            tap_time: Duration::from_millis(200),
+            double_tap_time: Duration::from_millis(300),
+            double_tap_distance: 10.0,
+            long_press_time: Duration::from_millis(500),
+            pinch_threshold: 0.1,
+            rotate_threshold: 15.0,
+            edge_margin: 5.0,
+            edge_swipe_distance: 20.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Mode {
+    // Fingers down but not yet moved, could still be a tap or long press.
+    Pending,
+    Pan(u8),
+    Pinch,
+    Edge(Edge),
+    // Gesture reported, ignore the rest of the sequence.
+    Done,
+}
+
+/// Gesture recognition for touchscreens, which libinput doesn't do.
+///
+/// A sequence starts with the first finger down and ends when all fingers
+/// are lifted. Only one device is tracked at a time; touches on other devices
+/// are ignored until the current sequence ends. Long presses are detected on
+/// the next touch frame, or earlier by calling poll() at next_deadline().
+pub struct TouchGestures {
+    config: TouchConfig,
+    device: Option<Device>,
+    slots: BTreeMap<i32, (f64, f64)>,
+    mode: Mode,
+    down_time: Timestamp,
+    start: (f64, f64),
+    max_fingers: usize,
+    edge: Option<Edge>,
+    centroid: Option<(f64, f64)>,
+    pinch_base: Option<(f64, f64)>,
+    last_tap: Option<(Timestamp, f64, f64)>,
+}
+
+impl TouchGestures {
+    pub fn new(config: TouchConfig) -> TouchGestures {
+        TouchGestures {
+            config: config,
+            device: None,
+            slots: BTreeMap::new(),
+            mode: Mode::Done,
+            down_time: Timestamp::from_usec(0),
+            start: (0.0, 0.0),
+            max_fingers: 0,
+            edge: None,
+            centroid: None,
+            pinch_base: None,
+            last_tap: None,
+        }
+    }
+
+    pub fn config(&self) -> &TouchConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: TouchConfig) {
+        self.config = config;
+    }
+
+    pub fn feed(&mut self, event: &Event) -> Vec<TouchGesture> {
+        let mut gestures = Vec::new();
+        let device = event.device();
+        // Only device removal has no time, and it never completes a tap.
+        let time = event.timestamp().unwrap_or(self.down_time);
+
+        if let EventType::TouchDown(touch) = event.event_type() {
+            if self.device.is_none() {
+                self.device = Some(device.clone());
+                let edge = self.edge_at(device.size(), touch.x, touch.y);
+                self.begin(time, touch.x, touch.y, edge);
+            }
+        }
+
+        if self.device.as_ref() != Some(device) {
+            return gestures;
+        }
+
+        match event.event_type() {
+            EventType::TouchDown(touch) => self.down(touch.slot, touch.x, touch.y),
+            EventType::TouchMotion(touch) => self.motion(touch.slot, touch.x, touch.y),
+            EventType::TouchUp(slot) => {
+                self.slots.remove(&slot);
+            },
+            EventType::TouchCancel(_) | EventType::DeviceRemove => self.cancel(time, &mut gestures),
+            EventType::TouchFrame => {
+                self.frame(time, &mut gestures);
+            },
+            _ => {},
+        }
+
+        gestures
+    }
+
+    /// Time a long press becomes due, if one is possible right now.
+    pub fn next_deadline(&self) -> Option<Timestamp> {
+        // A long press time too long to represent never becomes due.
+        if self.long_press_possible() {
+            self.down_time.checked_add(self.config.long_press_time)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Reports a long press if it is due at `now`.
+    pub fn poll(&mut self, now: Timestamp) -> Vec<TouchGesture> {
+        let mut gestures = Vec::new();
+        self.check_long_press(now, &mut gestures);
+        gestures
+    }
+
+    fn begin(&mut self, time: Timestamp, x: f64, y: f64, edge: Option<Edge>) {
+        self.mode = Mode::Pending;
+        self.down_time = time;
+        self.start = (x, y);
+        self.max_fingers = 0;
+        self.edge = edge;
+        self.centroid = None;
+        self.pinch_base = None;
+    }
+
+    fn down(&mut self, slot: i32, x: f64, y: f64) {
+        self.slots.insert(slot, (x, y));
+        self.max_fingers = self.max_fingers.max(self.slots.len());
+    }
+
+    fn motion(&mut self, slot: i32, x: f64, y: f64) {
+        if let Some(position) = self.slots.get_mut(&slot) {
+            *position = (x, y);
+        }
+    }
+
+    // Closes any running gesture but doesn't turn it into a tap.
+    fn cancel(&mut self, time: Timestamp, gestures: &mut Vec<TouchGesture>) {
+        self.mode = match self.mode {
+            Mode::Pending => Mode::Done,
+            mode => mode,
+        };
+        self.slots.clear();
+        self.end(time, gestures);
+    }
+
+    fn frame(&mut self, time: Timestamp, gestures: &mut Vec<TouchGesture>) {
+        if self.slots.is_empty() {
+            self.end(time, gestures);
+            return;
+        }
+
+        self.check_long_press(time, gestures);
+
+        let fingers = self.slots.len();
+        match self.mode {
+            Mode::Pending if self.max_fingers == 1 => {
+                let position = self.centroid_now();
+                if distance(self.start, position) > self.config.tap_distance {
+                    self.mode = match self.edge {
+                        Some(edge) => Mode::Edge(edge),
+                        None => {
+                            self.centroid = Some(self.start);
+                            Mode::Pan(1)
+                        },
+                    };
+                }
+            },
+            Mode::Pending if fingers >= 2 => {
+                let (base_distance, base_angle) = match self.pinch_base {
+                    Some(base) => base,
+                    None => {
+                        self.pinch_base = self.two_fingers();
+                        self.centroid = Some(self.centroid_now());
+                        return;
+                    },
+                };
+                let (finger_distance, angle) = self.two_fingers().unwrap_or((base_distance, base_angle));
+
+                if (finger_distance / base_distance - 1.0).abs() > self.config.pinch_threshold
+                   || angle_delta(base_angle, angle).abs() > self.config.rotate_threshold {
+                    self.mode = Mode::Pinch;
+                }
+                else if let Some(centroid) = self.centroid {
+                    if distance(centroid, self.centroid_now()) > self.config.tap_distance {
+                        self.mode = Mode::Pan(fingers as u8);
+                    }
+                }
+            },
+            _ => {},
+        }
+
+        match self.mode {
+            Mode::Pan(_) => {
+                let centroid = self.centroid_now();
+                let previous = self.centroid.unwrap_or(centroid);
+                self.centroid = Some(centroid);
+
+                // A finger landing or lifting moves the centroid, rebase
+                // instead of reporting a jump.
+                if fingers as u8 != self.pan_fingers() {
+                    self.mode = Mode::Pan(fingers as u8);
+                }
+                else if centroid != previous {
+                    gestures.push(TouchGesture::Pan(fingers as u8, centroid.0 - previous.0, centroid.1 - previous.1));
+                }
+            },
+            Mode::Pinch => {
+                if let (Some((base_distance, base_angle)), Some((finger_distance, angle))) = (self.pinch_base, self.two_fingers()) {
+                    gestures.push(TouchGesture::Pinch(finger_distance / base_distance));
+                    gestures.push(TouchGesture::Rotate(angle_delta(base_angle, angle)));
+                }
+            },
+            Mode::Edge(edge) => {
+                if fingers > 1 {
+                    self.mode = Mode::Done;
+                    return;
+                }
+
+                let (x, y) = self.centroid_now();
+                let inwards = match edge {
+                    Edge::Left => x - self.start.0,
+                    Edge::Right => self.start.0 - x,
+                    Edge::Top => y - self.start.1,
+                    Edge::Bottom => self.start.1 - y,
+                };
+
+                if inwards >= self.config.edge_swipe_distance {
+                    gestures.push(TouchGesture::EdgeSwipe(edge));
+                    self.mode = Mode::Done;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn end(&mut self, time: Timestamp, gestures: &mut Vec<TouchGesture>) {
+        match self.mode {
+            Mode::Pending if self.max_fingers == 1 && time.duration_since(self.down_time) <= self.config.tap_time => {
+                let (x, y) = self.start;
+                gestures.push(TouchGesture::Tap(x, y));
+
+                let double_tap = match self.last_tap {
+                    Some((last_time, last_x, last_y)) => {
+                        time.duration_since(last_time) <= self.config.double_tap_time
+                            && distance((last_x, last_y), (x, y)) <= self.config.double_tap_distance
+                    },
+                    None => false,
+                };
+
+                if double_tap {
+                    gestures.push(TouchGesture::DoubleTap(x, y));
+                    self.last_tap = None;
+                }
+                else {
+                    self.last_tap = Some((time, x, y));
+                }
+            },
+            Mode::Pan(fingers) => gestures.push(TouchGesture::PanEnd(fingers)),
+            Mode::Pinch => gestures.push(TouchGesture::PinchEnd),
+            _ => {},
+        }
+
+        self.device = None;
+        self.mode = Mode::Done;
+    }
+
+    fn long_press_possible(&self) -> bool {
+        self.mode == Mode::Pending && self.max_fingers == 1 && self.slots.len() == 1
+    }
+
+    fn check_long_press(&mut self, now: Timestamp, gestures: &mut Vec<TouchGesture>) {
+        if self.long_press_possible() && now.duration_since(self.down_time) >= self.config.long_press_time {
+            let (x, y) = self.start;
+            gestures.push(TouchGesture::LongPress(x, y));
+            self.mode = Mode::Done;
+        }
+    }
+
+    fn pan_fingers(&self) -> u8 {
+        match self.mode {
+            Mode::Pan(fingers) => fingers,
+            _ => 0,
+        }
+    }
+
+    fn centroid_now(&self) -> (f64, f64) {
+        let count = self.slots.len().max(1) as f64;
+        let (x, y) = self.slots.values().fold((0.0, 0.0), |(x, y), &(px, py)| (x + px, y + py));
+
+        (x / count, y / count)
+    }
+
+    // Distance and angle between the two lowest slots.
+    fn two_fingers(&self) -> Option<(f64, f64)> {
+        let mut positions = self.slots.values();
+        match (positions.next(), positions.next()) {
+            (Some(&first), Some(&second)) => {
+                let angle = (second.1 - first.1).atan2(second.0 - first.0).to_degrees();
+                Some((distance(first, second).max(::std::f64::EPSILON), angle))
+            },
+            _ => None,
+        }
+    }
+
+    // Edge of a device of the given size that the position is close to.
+    fn edge_at(&self, size: Option<(f64, f64)>, x: f64, y: f64) -> Option<Edge> {
+        let (width, height) = match size {
+            Some(size) => size,
+            None => return None,
+        };
+        let margin = self.config.edge_margin;
+
+        if x < margin {
+            Some(Edge::Left)
+        }
+        else if x > width - margin {
+            Some(Edge::Right)
+        }
+        else if y < margin {
+            Some(Edge::Top)
+        }
+        else if y > height - margin {
+            Some(Edge::Bottom)
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl Default for TouchGestures {
+    fn default() -> TouchGestures {
+        TouchGestures::new(TouchConfig::default())
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+// Difference between two angles in degrees, in -180..180.
+fn angle_delta(from: f64, to: f64) -> f64 {
+    let delta = (to - from) % 360.0;
+    if delta > 180.0 {
+        delta - 360.0
+    }
+    else if delta < -180.0 {
+        delta + 360.0
+    }
+    else {
+        delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::std::time::Duration;
+    use super::{angle_delta, distance, Edge, TouchConfig, TouchGesture, TouchGestures};
+    use time::Timestamp;
+
+    fn ms(ms: u64) -> Timestamp {
+        Timestamp::from_usec(ms * 1000)
+    }
+
+    fn frame(touch: &mut TouchGestures, time: u64) -> Vec<TouchGesture> {
+        let mut gestures = Vec::new();
+        touch.frame(ms(time), &mut gestures);
+        gestures
+    }
+
+    // One finger down and up again at the same place.
+    fn tap(touch: &mut TouchGestures, down: u64, up: u64, x: f64, y: f64) -> Vec<TouchGesture> {
+        touch.begin(ms(down), x, y, None);
+        touch.down(0, x, y);
+        assert_eq!(frame(touch, down), vec![]);
+        touch.slots.remove(&0);
+        frame(touch, up)
+    }
+
+    #[test]
+    fn distance_is_euclidean() {
+        assert_eq!(distance((1.0, 2.0), (4.0, 6.0)), 5.0);
+        assert_eq!(distance((4.0, 6.0), (1.0, 2.0)), 5.0);
+        assert_eq!(distance((1.0, 1.0), (1.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn angle_delta_wraps_around() {
+        assert_eq!(angle_delta(10.0, 30.0), 20.0);
+        assert_eq!(angle_delta(30.0, 10.0), -20.0);
+        assert_eq!(angle_delta(350.0, 10.0), 20.0);
+        assert_eq!(angle_delta(10.0, 350.0), -20.0);
+        assert_eq!(angle_delta(170.0, -170.0), 20.0);
+        assert_eq!(angle_delta(-170.0, 170.0), -20.0);
+        assert_eq!(angle_delta(730.0, 0.0), -10.0);
+        assert_eq!(angle_delta(0.0, 180.0), 180.0);
+    }
+
+    #[test]
+    fn short_touch_is_tap() {
+        let mut touch = TouchGestures::default();
+        assert_eq!(tap(&mut touch, 0, 100, 10.0, 20.0), vec![TouchGesture::Tap(10.0, 20.0)]);
+        assert_eq!(tap(&mut touch, 1000, 1300, 10.0, 20.0), vec![]);
+    }
+
+    #[test]
+    fn second_tap_close_by_is_double_tap() {
+        let mut touch = TouchGestures::default();
+        assert_eq!(tap(&mut touch, 0, 50, 10.0, 10.0), vec![TouchGesture::Tap(10.0, 10.0)]);
+        assert_eq!(tap(&mut touch, 150, 200, 15.0, 10.0),
+                   vec![TouchGesture::Tap(15.0, 10.0), TouchGesture::DoubleTap(15.0, 10.0)]);
+        // The double tap used up the first tap.
+        assert_eq!(tap(&mut touch, 300, 350, 15.0, 10.0), vec![TouchGesture::Tap(15.0, 10.0)]);
+        // Too far away, then too late.
+        assert_eq!(tap(&mut touch, 400, 450, 40.0, 10.0), vec![TouchGesture::Tap(40.0, 10.0)]);
+        assert_eq!(tap(&mut touch, 1000, 1050, 40.0, 10.0), vec![TouchGesture::Tap(40.0, 10.0)]);
+    }
+
+    #[test]
+    fn held_touch_is_long_press() {
+        let mut touch = TouchGestures::default();
+        touch.begin(ms(100), 10.0, 20.0, None);
+        touch.down(0, 10.0, 20.0);
+        assert_eq!(frame(&mut touch, 100), vec![]);
+        assert_eq!(touch.next_deadline(), Some(ms(600)));
+
+        assert_eq!(touch.poll(ms(599)), vec![]);
+        assert_eq!(touch.poll(ms(600)), vec![TouchGesture::LongPress(10.0, 20.0)]);
+        assert_eq!(touch.next_deadline(), None);
+
+        // Lifting the finger afterwards isn't a tap.
+        touch.slots.remove(&0);
+        assert_eq!(frame(&mut touch, 700), vec![]);
+    }
+
+    #[test]
+    fn unrepresentable_long_press_never_becomes_due() {
+        let mut touch = TouchGestures::new(TouchConfig { long_press_time: Duration::new(::std::u64::MAX, 0), ..TouchConfig::default() });
+        touch.begin(ms(100), 10.0, 20.0, None);
+        touch.down(0, 10.0, 20.0);
+        assert_eq!(frame(&mut touch, 100), vec![]);
+        assert_eq!(touch.next_deadline(), None);
+        assert_eq!(touch.poll(ms(1000)), vec![]);
+    }
+
+    #[test]
+    fn moving_touch_pans() {
+        let mut touch = TouchGestures::default();
+        touch.begin(ms(0), 10.0, 10.0, None);
+        touch.down(0, 10.0, 10.0);
+        assert_eq!(frame(&mut touch, 0), vec![]);
+
+        // Within the tap distance nothing happens yet.
+        touch.motion(0, 12.0, 10.0);
+        assert_eq!(frame(&mut touch, 10), vec![]);
+        touch.motion(0, 20.0, 10.0);
+        assert_eq!(frame(&mut touch, 20), vec![TouchGesture::Pan(1, 10.0, 0.0)]);
+        touch.motion(0, 20.0, 15.0);
+        assert_eq!(frame(&mut touch, 30), vec![TouchGesture::Pan(1, 0.0, 5.0)]);
+        assert_eq!(touch.next_deadline(), None);
+
+        touch.slots.remove(&0);
+        assert_eq!(frame(&mut touch, 40), vec![TouchGesture::PanEnd(1)]);
+    }
+
+    #[test]
+    fn spreading_fingers_pinch() {
+        let mut touch = TouchGestures::default();
+        touch.begin(ms(0), 10.0, 10.0, None);
+        touch.down(0, 10.0, 10.0);
+        assert_eq!(frame(&mut touch, 0), vec![]);
+        touch.down(1, 20.0, 10.0);
+        assert_eq!(frame(&mut touch, 10), vec![]);
+
+        touch.motion(1, 30.0, 10.0);
+        assert_eq!(frame(&mut touch, 20), vec![TouchGesture::Pinch(2.0), TouchGesture::Rotate(0.0)]);
+        touch.motion(1, 10.0, 30.0);
+        assert_eq!(frame(&mut touch, 30), vec![TouchGesture::Pinch(2.0), TouchGesture::Rotate(90.0)]);
+
+        touch.slots.remove(&1);
+        assert_eq!(frame(&mut touch, 40), vec![]);
+        touch.slots.remove(&0);
+        assert_eq!(frame(&mut touch, 50), vec![TouchGesture::PinchEnd]);
+    }
+
+    #[test]
+    fn touch_from_edge_swipes() {
+        let mut touch = TouchGestures::default();
+        touch.begin(ms(0), 2.0, 50.0, Some(Edge::Left));
+        touch.down(0, 2.0, 50.0);
+        assert_eq!(frame(&mut touch, 0), vec![]);
+
+        touch.motion(0, 10.0, 50.0);
+        assert_eq!(frame(&mut touch, 10), vec![]);
+        touch.motion(0, 22.0, 50.0);
+        assert_eq!(frame(&mut touch, 20), vec![TouchGesture::EdgeSwipe(Edge::Left)]);
+        touch.motion(0, 40.0, 50.0);
+        assert_eq!(frame(&mut touch, 30), vec![]);
+
+        touch.slots.remove(&0);
+        assert_eq!(frame(&mut touch, 40), vec![]);
+    }
+
+    #[test]
+    fn edges_are_within_the_margin() {
+        let touch = TouchGestures::default();
+        let size = Some((100.0, 50.0));
+        assert_eq!(touch.edge_at(size, 2.0, 25.0), Some(Edge::Left));
+        assert_eq!(touch.edge_at(size, 98.0, 25.0), Some(Edge::Right));
+        assert_eq!(touch.edge_at(size, 50.0, 2.0), Some(Edge::Top));
+        assert_eq!(touch.edge_at(size, 50.0, 48.0), Some(Edge::Bottom));
+        assert_eq!(touch.edge_at(size, 50.0, 25.0), None);
+        assert_eq!(touch.edge_at(None, 2.0, 25.0), None);
+    }
+}
@@ -0,0 +1,148 @@
+//! udev side of a libinput device, for matching devices on udev properties.
+
+use ::libinput_sys::*;
+use ::std::ffi::{CStr, CString, OsStr};
+use ::std::os::raw::c_char;
+use ::std::os::unix::ffi::OsStrExt;
+use ::std::path::Path;
+
+// Strings live as long as the udev device they were read from.
+unsafe fn opt_str<'a>(buf: *const c_char) -> Option<&'a str> {
+    if buf.is_null() {
+        None
+    }
+    else {
+        ::std::str::from_utf8(CStr::from_ptr(buf).to_bytes()).ok()
+    }
+}
+
+unsafe fn opt_path<'a>(buf: *const c_char) -> Option<&'a Path> {
+    if buf.is_null() {
+        None
+    }
+    else {
+        Some(Path::new(OsStr::from_bytes(CStr::from_ptr(buf).to_bytes())))
+    }
+}
+
+/// A udev device. Holds a reference, like Device.
+pub struct UdevDevice {
+    handle: *mut udev_device,
+}
+
+impl UdevDevice {
+    // Takes ownership of a reference. None for null.
+    pub(crate) unsafe fn from_raw(handle: *mut udev_device) -> Option<UdevDevice> {
+        if handle.is_null() {
+            None
+        }
+        else {
+            Some(UdevDevice { handle: handle })
+        }
+    }
+
+    /// Device node, e.g. /dev/input/event3. None for devices without one,
+    /// such as the USB parent.
+    pub fn devnode(&self) -> Option<&Path> {
+        unsafe { opt_path(udev_device_get_devnode(self.handle)) }
+    }
+
+    pub fn syspath(&self) -> &Path {
+        unsafe { opt_path(udev_device_get_syspath(self.handle)).unwrap_or(Path::new("")) }
+    }
+
+    pub fn sysname(&self) -> &str {
+        unsafe { opt_str(udev_device_get_sysname(self.handle)).unwrap_or("") }
+    }
+
+    pub fn subsystem(&self) -> Option<&str> {
+        unsafe { opt_str(udev_device_get_subsystem(self.handle)) }
+    }
+
+    pub fn devtype(&self) -> Option<&str> {
+        unsafe { opt_str(udev_device_get_devtype(self.handle)) }
+    }
+
+    /// Value of a udev property, e.g. "ID_INPUT_TOUCHPAD", "ID_PATH" or a
+    /// "LIBINPUT_*" hwdb override.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        let name = match CString::new(name) {
+            Ok(name) => name,
+            Err(_) => return None,
+        };
+
+        unsafe { opt_str(udev_device_get_property_value(self.handle, name.as_ptr())) }
+    }
+
+    /// All udev properties as (name, value) pairs.
+    pub fn properties(&self) -> Vec<(&str, &str)> {
+        let mut properties = Vec::new();
+
+        unsafe {
+            let mut entry = udev_device_get_properties_list_entry(self.handle);
+            while !entry.is_null() {
+                if let (Some(name), Some(value)) = (opt_str(udev_list_entry_get_name(entry)),
+                                                    opt_str(udev_list_entry_get_value(entry))) {
+                    properties.push((name, value));
+                }
+                entry = udev_list_entry_get_next(entry);
+            }
+        }
+
+        properties
+    }
+
+    /// Whether the property is set to "1", as the ID_INPUT_* flags are.
+    pub fn property_is_set(&self, name: &str) -> bool {
+        self.property(name) == Some("1")
+    }
+
+    pub fn parent(&self) -> Option<UdevDevice> {
+        unsafe {
+            // The parent belongs to the child, take our own reference.
+            let parent = udev_device_get_parent(self.handle);
+            UdevDevice::from_raw(if parent.is_null() { parent } else { udev_device_ref(parent) })
+        }
+    }
+
+    /// Closest parent in the given subsystem, optionally also of the given
+    /// devtype.
+    pub fn parent_with_subsystem(&self, subsystem: &str, devtype: Option<&str>) -> Option<UdevDevice> {
+        let subsystem = match CString::new(subsystem) {
+            Ok(subsystem) => subsystem,
+            Err(_) => return None,
+        };
+        let devtype = match devtype.map(CString::new) {
+            Some(Ok(devtype)) => Some(devtype),
+            Some(Err(_)) => return None,
+            None => None,
+        };
+
+        unsafe {
+            let parent = udev_device_get_parent_with_subsystem_devtype(
+                self.handle,
+                subsystem.as_ptr(),
+                devtype.as_ref().map_or(::std::ptr::null(), |devtype| devtype.as_ptr()));
+            UdevDevice::from_raw(if parent.is_null() { parent } else { udev_device_ref(parent) })
+        }
+    }
+
+    /// The USB device the input device belongs to, None if not on USB.
+    pub fn usb_device(&self) -> Option<UdevDevice> {
+        self.parent_with_subsystem("usb", Some("usb_device"))
+    }
+}
+
+impl Clone for UdevDevice {
+    fn clone(&self) -> UdevDevice {
+        unsafe { udev_device_ref(self.handle) };
+
+        UdevDevice { handle: self.handle }
+    }
+}
+
+impl Drop for UdevDevice {
+    fn drop(&mut self) {
+        unsafe { udev_device_unref(self.handle) };
+    }
+}
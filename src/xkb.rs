@@ -0,0 +1,167 @@
+//! Keymap handling through xkbcommon. Enabled with the `xkb` feature.
+
+use ::xkbcommon::xkb;
+use events::{Event, EventType, Leds, State};
+use keys::Key;
+use leds::Keyboards;
+
+// Offset between evdev key codes and X11/xkb keycodes.
+const EVDEV_OFFSET: u32 = 8;
+
+/// Keymap names in RMLVO form. Empty strings select the system defaults.
+#[derive(Clone, Debug, Default)]
+pub struct Rmlvo {
+    pub rules: String,
+    pub model: String,
+    pub layout: String,
+    pub variant: String,
+    pub options: Option<String>,
+}
+
+/// Currently active modifiers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+    pub caps_lock: bool,
+    pub num_lock: bool,
+}
+
+/// Result of feeding a key event through the keymap.
+#[derive(Clone, Debug)]
+pub struct KeyOutput {
+    pub key: Key,
+    pub state: State,
+    /// All keysyms the key produces, usually exactly one.
+    pub keysyms: Vec<u32>,
+    /// UTF-8 text of the key press. Empty on release and for keys without text.
+    pub text: String,
+}
+
+/// Keyboard state of a single seat.
+/// Feed it all events of the context; events from other seats are ignored.
+/// The lock LEDs of every keyboard on the seat are kept in sync with the xkb
+/// state, keyboards are picked up when added.
+pub struct KeyboardState {
+    seat: String,
+    state: xkb::State,
+    keyboards: Keyboards,
+}
+
+impl KeyboardState {
+    pub fn new(seat: &str, names: &Rmlvo) -> Result<KeyboardState, &'static str> {
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let keymap = xkb::Keymap::new_from_names(&context,
+                                                 &names.rules,
+                                                 &names.model,
+                                                 &names.layout,
+                                                 &names.variant,
+                                                 names.options.clone(),
+                                                 xkb::KEYMAP_COMPILE_NO_FLAGS);
+
+        let keymap = match keymap {
+            Some(keymap) => keymap,
+            None => return Err("Failed to compile keymap"),
+        };
+
+        Ok(KeyboardState {
+            seat: seat.to_owned(),
+            state: xkb::State::new(&keymap),
+            keyboards: Keyboards::default(),
+        })
+    }
+
+    pub fn seat(&self) -> &str {
+        &self.seat
+    }
+
+    /// Updates the state with a key event.
+    /// Returns None for non-keyboard events and events from other seats.
+    pub fn feed(&mut self, event: &Event) -> Option<KeyOutput> {
+        if event.device().logical_seat() != self.seat {
+            return None;
+        }
+        self.keyboards.feed(event);
+
+        match event.event_type() {
+            EventType::Keyboard(key_event) => {
+                let keycode = key_event.key.code() + EVDEV_OFFSET;
+
+                // Keysyms and text depend on the modifiers before this key.
+                let keysyms = self.state.key_get_syms(keycode).to_vec();
+                let text = match key_event.state {
+                    State::Pressed => self.state.key_get_utf8(keycode),
+                    State::Released => String::new(),
+                };
+
+                // Only the first press and last release on the seat change the
+                // xkb state, otherwise a key held on two keyboards would
+                // toggle locks twice or release modifiers early.
+                let direction = match (key_event.state, key_event.seat_key_count) {
+                    (State::Pressed, 1) => Some(xkb::KeyDirection::Down),
+                    (State::Released, 0) => Some(xkb::KeyDirection::Up),
+                    _ => None,
+                };
+                if let Some(direction) = direction {
+                    self.state.update_key(keycode, direction);
+                    self.sync_leds();
+                }
+
+                Some(KeyOutput {
+                    key: key_event.key,
+                    state: key_event.state,
+                    keysyms: keysyms,
+                    text: text,
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// Keysym the key would currently produce, without changing the state.
+    pub fn keysym(&self, key: Key) -> u32 {
+        self.state.key_get_one_sym(key.code() + EVDEV_OFFSET)
+    }
+
+    /// Text the key would currently produce, without changing the state.
+    pub fn text(&self, key: Key) -> String {
+        self.state.key_get_utf8(key.code() + EVDEV_OFFSET)
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        let active = |name| self.state.mod_name_is_active(name, xkb::STATE_MODS_EFFECTIVE);
+
+        Modifiers {
+            shift: active(xkb::MOD_NAME_SHIFT),
+            ctrl: active(xkb::MOD_NAME_CTRL),
+            alt: active(xkb::MOD_NAME_ALT),
+            logo: active(xkb::MOD_NAME_LOGO),
+            caps_lock: active(xkb::MOD_NAME_CAPS),
+            num_lock: active(xkb::MOD_NAME_NUM),
+        }
+    }
+
+    /// Index of the active layout in the keymap.
+    pub fn layout(&self) -> u32 {
+        self.state.serialize_layout(xkb::STATE_LAYOUT_EFFECTIVE)
+    }
+
+    fn sync_leds(&mut self) {
+        let mut leds = Leds::empty();
+        if self.state.led_name_is_active(xkb::LED_NAME_NUM) {
+            leds |= Leds::NUM_LOCK;
+        }
+        if self.state.led_name_is_active(xkb::LED_NAME_CAPS) {
+            leds |= Leds::CAPS_LOCK;
+        }
+        if self.state.led_name_is_active(xkb::LED_NAME_SCROLL) {
+            leds |= Leds::SCROLL_LOCK;
+        }
+
+        if leds != self.keyboards.leds() {
+            self.keyboards.set_leds(leds);
+        }
+    }
+}